@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use victron_ble::*;
+
+const KEY: &str = "0df4d0395b7d1a876c0c33ecb9e70dcd";
+
+fn solar_charger_advertisement(key: &EncryptionKey) -> [u8; 24] {
+    let device_state = DeviceState::SolarCharger(SolarChargerState {
+        mode: Mode::Bulk,
+        error_state: ErrorState::NoError,
+        battery_voltage_v: Some(13.2),
+        battery_current_a: Some(5.0),
+        yield_today_kwh: Some(1.25),
+        pv_power_w: Some(72.0),
+        load_current_a: None,
+    });
+    encode_manufacturer_data(&device_state, 0xA055, 0x2221, key).unwrap()
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let key = EncryptionKey::from_hex(KEY).unwrap();
+    let advertisement = solar_charger_advertisement(&key);
+
+    c.bench_function("parse_manufacturer_data", |b| {
+        b.iter(|| parse_manufacturer_data(black_box(&advertisement), black_box(&key)).unwrap())
+    });
+
+    let decoder = Decoder::new(&key);
+    c.bench_function("Decoder::decode", |b| {
+        b.iter(|| decoder.decode(black_box(&advertisement)).unwrap())
+    });
+
+    let mut payload = [0; 16];
+    c.bench_function("Decoder::decrypt_into", |b| {
+        b.iter(|| {
+            decoder
+                .decrypt_into(black_box(&advertisement), &mut payload)
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_decode);
+criterion_main!(benches);
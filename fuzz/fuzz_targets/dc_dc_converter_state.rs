@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    victron_ble::fuzzing::run_target("dc_dc_converter_state", data);
+});
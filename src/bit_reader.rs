@@ -30,55 +30,65 @@ impl<'a> BitReader<'a> {
     }
 
     pub fn read_signed_field(&mut self, num_bits: usize, na_value: i64, scale: f32) -> Result<Option<f32>> {
-        let int_val = self.read_signed_int(num_bits)?;
-        if int_val == na_value {
+        // The NA value can have the sign bit set, so compare the raw bits before sign extending
+        let raw_val = self.read_unsigned_int(num_bits)?;
+        if raw_val == na_value as u64 {
             return Ok(None);
         }
+        let int_val = sign_extend(raw_val, num_bits);
 
         let val = (int_val as f32) * scale;
         
         Ok(Some(val))
     }
 
+    /// Read an unsigned integer of up to 64 bits.
     pub fn read_unsigned_int(&mut self, num_bits: usize) -> Result<u64> {
-        let mut value = 0u64;
-        for position in 0..num_bits {
-            value |= if self.read_bit()? { 1u64 } else { 0u64 } << position
+        debug_assert!(num_bits <= 64);
+        if num_bits == 0 {
+            return Ok(0);
         }
-        Ok(value)
-    }
 
-    pub fn read_signed_int(&mut self, num_bits: usize) -> Result<i64> {
-        let mut value = 0i64;
-        for position in 0..num_bits - 1 {
-            value |= if self.read_bit()? { 1i64 } else { 0i64 } << position
-        }
-        if self.read_bit()? {
-            value -= 1i64 << (num_bits - 1)
+        let end = self.cursor + num_bits;
+        if end > self.data.len() * 8 {
+            return Err(Error::DataTooShort);
         }
-        Ok(value)
+
+        // A field of up to 64 bits spans at most 9 bytes, so load them as one word
+        // rather than reading bit by bit.
+        let bytes = &self.data[self.cursor / 8..end.div_ceil(8)];
+        let mut word = [0u8; 16];
+        word[..bytes.len()].copy_from_slice(bytes);
+        let value = (u128::from_le_bytes(word) >> (self.cursor % 8)) as u64;
+
+        self.cursor = end;
+
+        Ok(value & (u64::MAX >> (64 - num_bits)))
     }
 
-    pub fn skip(&mut self, num_bits: usize) -> Result<()> {
-        self.read_unsigned_int(num_bits)?;
-        Ok(())
+    /// Read a two's complement signed integer of up to 64 bits.
+    pub fn read_signed_int(&mut self, num_bits: usize) -> Result<i64> {
+        let value = self.read_unsigned_int(num_bits)?;
+        Ok(sign_extend(value, num_bits))
     }
 
-    fn read_bit(&mut self) -> Result<bool> {
-        if self.cursor == self.data.len() * 8 {
+    pub fn skip(&mut self, num_bits: usize) -> Result<()> {
+        let end = self.cursor + num_bits;
+        if end > self.data.len() * 8 {
             return Err(Error::DataTooShort);
         }
+        self.cursor = end;
+        Ok(())
+    }
+}
 
-        let byte = self.cursor / 8;
-        let bit = self.cursor % 8;
-        let byte_val = self.data[byte];
-        let bit_val = (byte_val >> bit) & 1;
-        let is_bit_set = bit_val == 1;
-
-        self.cursor += 1;
-
-        Ok(is_bit_set)
+/// Interpret the lowest `num_bits` bits of the value as a two's complement signed integer.
+fn sign_extend(value: u64, num_bits: usize) -> i64 {
+    if num_bits == 0 {
+        return 0;
     }
+    let shift = 64 - num_bits;
+    ((value << shift) as i64) >> shift
 }
 
 mod test {
@@ -89,15 +99,32 @@ mod test {
         let data = hex::decode("1a2b3c4d5e6f7890").unwrap();
         let mut reader = BitReader::new(&data[..]);
 
-        assert!(!reader.read_bit().unwrap());
-        assert!(reader.read_bit().unwrap());
-        assert!(!reader.read_bit().unwrap());
-        assert!(reader.read_bit().unwrap());
+        assert_eq!(reader.read_unsigned_int(1).unwrap(), 0);
+        assert_eq!(reader.read_unsigned_int(1).unwrap(), 1);
+        assert_eq!(reader.read_unsigned_int(1).unwrap(), 0);
+        assert_eq!(reader.read_unsigned_int(1).unwrap(), 1);
         assert!(reader.read_unsigned_int(6).unwrap() == 0x31);
         assert!(reader.read_signed_int(6).unwrap() == 0x0A);
         assert_eq!(reader.read_signed_int(4).unwrap(), -0x04);
         assert!(reader.read_unsigned_int(11).unwrap() == 0x4D3);
-        assert!(!reader.read_bit().unwrap());
+        assert_eq!(reader.read_unsigned_int(1).unwrap(), 0);
         assert!(reader.read_unsigned_int(32).unwrap() == 0x90786F5E);
+        assert!(matches!(
+            reader.read_unsigned_int(1),
+            Err(crate::err::Error::DataTooShort)
+        ));
+    }
+
+    #[test]
+    fn test_read_signed_extremes() {
+        use crate::bit_reader::BitReader;
+
+        let data = hex::decode("00000000000000800100").unwrap();
+        let mut reader = BitReader::new(&data[..]);
+
+        assert_eq!(reader.read_signed_int(0).unwrap(), 0);
+        assert_eq!(reader.read_signed_int(64).unwrap(), i64::MIN);
+        assert_eq!(reader.read_signed_int(1).unwrap(), -1);
+        assert_eq!(reader.read_unsigned_int(15).unwrap(), 0);
     }
 }
@@ -0,0 +1,237 @@
+use crate::err::*;
+
+pub(crate) const PAYLOAD_LEN: usize = 16;
+
+pub(crate) struct BitWriter {
+    cursor: usize,
+    data: [u8; PAYLOAD_LEN],
+}
+
+/// Writes numbers in the packed binary format used by the Victron BLE data.
+///
+/// The inverse of `BitReader`. Unused bits at the end of the payload are set,
+/// as required by the protocol.
+impl BitWriter {
+    pub fn new() -> Self {
+        Self {
+            cursor: 0,
+            data: [0; PAYLOAD_LEN],
+        }
+    }
+
+    pub fn write_unsigned_field(
+        &mut self,
+        num_bits: usize,
+        na_value: u64,
+        scale: f32,
+        offset: f32,
+        value: Option<f32>,
+    ) -> Result<()> {
+        let int_val = match value {
+            None => na_value,
+            Some(value) => {
+                let int_val = unscale_unsigned(value, scale, offset)?;
+                if int_val == na_value {
+                    return Err(Error::ValueOutOfRange);
+                }
+                int_val
+            }
+        };
+
+        self.write_unsigned_int(num_bits, int_val)
+    }
+
+    pub fn write_signed_field(
+        &mut self,
+        num_bits: usize,
+        na_value: i64,
+        scale: f32,
+        value: Option<f32>,
+    ) -> Result<()> {
+        // The NA value is a raw bit pattern, which can have the sign bit set, so it is
+        // written and compared as unsigned bits.
+        let Some(value) = value else {
+            return self.write_unsigned_int(num_bits, na_value as u64);
+        };
+        let int_val = round(value / scale);
+        if int_val as u64 & (u64::MAX >> (64 - num_bits)) == na_value as u64 {
+            return Err(Error::ValueOutOfRange);
+        }
+
+        self.write_signed_int(num_bits, int_val)
+    }
+
+    pub fn write_unsigned_int(&mut self, num_bits: usize, value: u64) -> Result<()> {
+        if num_bits < 64 && value >> num_bits != 0 {
+            return Err(Error::ValueOutOfRange);
+        }
+        for position in 0..num_bits {
+            self.write_bit((value >> position) & 1 == 1)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_signed_int(&mut self, num_bits: usize, value: i64) -> Result<()> {
+        let min = -(1i64 << (num_bits - 1));
+        let max = (1i64 << (num_bits - 1)) - 1;
+        if value < min || value > max {
+            return Err(Error::ValueOutOfRange);
+        }
+        for position in 0..num_bits {
+            self.write_bit((value >> position) & 1 == 1)?;
+        }
+        Ok(())
+    }
+
+    /// Return the payload with all unused bits set.
+    pub fn finish(mut self) -> [u8; PAYLOAD_LEN] {
+        while self.write_bit(true).is_ok() {}
+        self.data
+    }
+
+    fn write_bit(&mut self, is_bit_set: bool) -> Result<()> {
+        if self.cursor == PAYLOAD_LEN * 8 {
+            return Err(Error::DataTooLong);
+        }
+
+        let byte = self.cursor / 8;
+        let bit = self.cursor % 8;
+        if is_bit_set {
+            self.data[byte] |= 1 << bit;
+        }
+
+        self.cursor += 1;
+
+        Ok(())
+    }
+}
+
+/// Convert a value back to the unsigned integer it is represented by in the packed format.
+pub(crate) fn unscale_unsigned(value: f32, scale: f32, offset: f32) -> Result<u64> {
+    u64::try_from(round((value - offset) / scale)).map_err(|_| Error::ValueOutOfRange)
+}
+
+/// Round to the nearest integer, away from zero on a tie. `f32::round` is not
+/// available in `no_std`.
+fn round(value: f32) -> i64 {
+    if value < 0.0 {
+        (value - 0.5) as i64
+    } else {
+        (value + 0.5) as i64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bit_reader::BitReader;
+
+    #[test]
+    fn test_write() {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(4, 0xA).unwrap();
+        writer.write_unsigned_int(6, 0x31).unwrap();
+        writer.write_signed_int(6, 0x0A).unwrap();
+        writer.write_signed_int(4, -0x04).unwrap();
+        writer.write_unsigned_int(11, 0x4D3).unwrap();
+        writer.write_unsigned_int(1, 0).unwrap();
+        writer.write_unsigned_int(32, 0x90786F5E).unwrap();
+        let data = writer.finish();
+
+        assert_eq!(
+            data.as_slice(),
+            hex::decode("1a2b3c4d5e6f7890ffffffffffffffff").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_fields_round_trip() {
+        let mut writer = BitWriter::new();
+
+        writer
+            .write_unsigned_field(16, 0xFFFF, 0.01, 0.0, Some(13.87))
+            .unwrap();
+        writer
+            .write_signed_field(22, 0x3FFFFF, 0.001, Some(-42.35))
+            .unwrap();
+        writer
+            .write_unsigned_field(7, 0x7F, 1.0, -40.0, Some(-12.0))
+            .unwrap();
+        writer.write_signed_field(16, 0x7FFF, 0.1, None).unwrap();
+        writer
+            .write_signed_field(22, 0x3FFFFF, 0.001, None)
+            .unwrap();
+        let data = writer.finish();
+        let mut reader = BitReader::new(&data);
+
+        assert!(
+            (reader
+                .read_unsigned_field(16, 0xFFFF, 0.01, 0.0)
+                .unwrap()
+                .unwrap()
+                - 13.87)
+                .abs()
+                < 0.001
+        );
+        assert!(
+            (reader
+                .read_signed_field(22, 0x3FFFFF, 0.001)
+                .unwrap()
+                .unwrap()
+                + 42.35)
+                .abs()
+                < 0.001
+        );
+        assert!(
+            (reader
+                .read_unsigned_field(7, 0x7F, 1.0, -40.0)
+                .unwrap()
+                .unwrap()
+                + 12.0)
+                .abs()
+                < f32::EPSILON
+        );
+        assert!(reader.read_signed_field(16, 0x7FFF, 0.1).unwrap().is_none());
+        assert!(reader
+            .read_signed_field(22, 0x3FFFFF, 0.001)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_write_out_of_range() {
+        let mut writer = BitWriter::new();
+
+        assert!(matches!(
+            writer.write_unsigned_int(4, 0x10),
+            Err(Error::ValueOutOfRange)
+        ));
+        assert!(matches!(
+            writer.write_signed_int(4, 8),
+            Err(Error::ValueOutOfRange)
+        ));
+        assert!(matches!(
+            writer.write_signed_int(4, -9),
+            Err(Error::ValueOutOfRange)
+        ));
+        assert!(matches!(
+            writer.write_unsigned_field(8, 0xFF, 1.0, 0.0, Some(-1.0)),
+            Err(Error::ValueOutOfRange)
+        ));
+        assert!(matches!(
+            writer.write_unsigned_field(8, 0xFF, 1.0, 0.0, Some(255.0)),
+            Err(Error::ValueOutOfRange)
+        ));
+        assert!(matches!(
+            writer.write_signed_field(22, 0x3FFFFF, 0.001, Some(-0.001)),
+            Err(Error::ValueOutOfRange)
+        ));
+        assert!(matches!(writer.write_unsigned_int(64, 0), Ok(())));
+        assert!(matches!(writer.write_unsigned_int(64, 0), Ok(())));
+        assert!(matches!(
+            writer.write_unsigned_int(1, 0),
+            Err(Error::DataTooLong)
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+use super::Advertisement;
+use crate::{AdvertisementHeader, Product};
+use std::collections::BTreeMap;
+
+/// A Victron device found by `discover`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredDevice {
+    /// The Bluetooth address of the device. On MacOS this is the peripheral identifier.
+    pub address: String,
+    /// The advertised name, if it was resolved during discovery.
+    pub name: Option<String>,
+    /// The most recent signal strength in dBm.
+    pub rssi: Option<i16>,
+    /// The product ID from the Instant Readout header, if one was seen.
+    pub product_id: Option<u16>,
+    /// The record type from the Instant Readout header, if one was seen.
+    pub record_type: Option<u8>,
+    /// Whether the device published Instant Readout data.
+    pub instant_readout: bool,
+}
+
+impl DiscoveredDevice {
+    /// Look up the product from the catalogue of known Victron products.
+    pub fn product(&self) -> Option<&'static Product> {
+        self.product_id.and_then(Product::from_id)
+    }
+}
+
+/// Collects what is seen of each device during discovery, keyed by address.
+#[derive(Default)]
+pub(crate) struct Inventory {
+    devices: BTreeMap<String, DiscoveredDevice>,
+}
+
+impl Inventory {
+    /// Record an advertisement published under the Victron company ID.
+    pub(crate) fn observe(&mut self, advertisement: Advertisement) {
+        let device = self
+            .devices
+            .entry(advertisement.address.clone())
+            .or_insert_with(|| DiscoveredDevice {
+                address: advertisement.address,
+                name: None,
+                rssi: None,
+                product_id: None,
+                record_type: None,
+                instant_readout: false,
+            });
+
+        // Keep what was learnt from earlier advertisements if this one lacks it.
+        if advertisement.name.is_some() {
+            device.name = advertisement.name;
+        }
+        if advertisement.rssi.is_some() {
+            device.rssi = advertisement.rssi;
+        }
+        if let Ok(header) = AdvertisementHeader::parse(&advertisement.manufacturer_data) {
+            device.product_id = Some(header.product_id);
+            device.record_type = Some(header.record_type);
+            device.instant_readout = true;
+        }
+    }
+
+    /// The devices seen, ordered by address.
+    pub(crate) fn into_devices(self) -> Vec<DiscoveredDevice> {
+        self.devices.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn advertisement(
+        address: &str,
+        name: Option<&str>,
+        rssi: Option<i16>,
+        data: &[u8],
+    ) -> Advertisement {
+        Advertisement {
+            address: address.into(),
+            name: name.map(Into::into),
+            rssi,
+            company_id: super::super::VICTRON_MANUFACTURER_ID,
+            manufacturer_data: data.into(),
+            received_at: std::time::SystemTime::now(),
+        }
+    }
+
+    #[test]
+    fn test_inventory() {
+        let mut inventory = Inventory::default();
+        let instant_readout = [0x10, 0x00, 0x55, 0xA0, 0x01, 0x21, 0x22, 0x0D, 0x00];
+
+        inventory.observe(advertisement("CC", None, None, &[0x02, 0x01]));
+        inventory.observe(advertisement("AA", None, Some(-70), &instant_readout));
+        inventory.observe(advertisement(
+            "AA",
+            Some("SmartSolar HQ"),
+            None,
+            &instant_readout,
+        ));
+
+        let devices = inventory.into_devices();
+
+        assert_eq!(
+            devices,
+            vec![
+                DiscoveredDevice {
+                    address: "AA".into(),
+                    name: Some("SmartSolar HQ".into()),
+                    rssi: Some(-70),
+                    product_id: Some(0xA055),
+                    record_type: Some(0x01),
+                    instant_readout: true,
+                },
+                DiscoveredDevice {
+                    address: "CC".into(),
+                    name: None,
+                    rssi: None,
+                    product_id: None,
+                    record_type: None,
+                    instant_readout: false,
+                },
+            ]
+        );
+        assert!(devices[0].product().is_some());
+    }
+}
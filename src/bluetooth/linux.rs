@@ -2,48 +2,147 @@
 
 //! Linux specific implementation
 
-use crate::{err::*, DeviceState};
-use bluer::{DeviceEvent, DeviceProperty};
+use super::{Advertisement, AdvertisementSource, AdvertisementStream};
+use crate::err::*;
+use bluer::{AdapterEvent, Address, DeviceEvent, DeviceProperty};
+use core::pin::Pin;
+use std::{collections::HashMap, time::SystemTime};
 use tokio::sync::mpsc::UnboundedSender;
-use tokio_stream::StreamExt;
+use tokio_stream::{wrappers::UnboundedReceiverStream, Stream, StreamExt, StreamMap};
 
-pub(crate) async fn open_stream(
-    target_device_name: String,
-    target_device_encryption_key: Vec<u8>,
-    mut sender: UnboundedSender<Result<DeviceState>>,
-) -> Result<()> {
-    let session = bluer::Session::new().await?;
-    let adapter = session.default_adapter().await?;
-    adapter.set_powered(true).await?;
+type DeviceEvents = Pin<Box<dyn Stream<Item = DeviceEvent> + Send>>;
+
+/// The advertisement source of the operating system, backed by BlueZ.
+#[derive(Debug, Clone, Default)]
+pub struct SystemAdvertisementSource;
+
+impl AdvertisementSource for SystemAdvertisementSource {
+    async fn advertisements(&self) -> Result<AdvertisementStream> {
+        let session = bluer::Session::new().await?;
+        let adapter = session.default_adapter().await?;
+        adapter.set_powered(true).await?;
+
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
 
+        tokio::spawn(async move {
+            // Keep the session alive for as long as the adapter is in use.
+            let _session = session;
+            if let Err(e) = scan(adapter, &sender).await {
+                let _ = sender.send(Err(e));
+            }
+        });
+
+        let advertisements: AdvertisementStream = Box::pin(UnboundedReceiverStream::new(receiver));
+        Ok(advertisements)
+    }
+}
+
+/// What is known of a device seen during the scan.
+struct DeviceInfo {
+    name: Option<String>,
+    rssi: Option<i16>,
+    manufacturer_data: HashMap<u16, Vec<u8>>,
+}
+
+async fn scan(
+    adapter: bluer::Adapter,
+    sender: &UnboundedSender<Result<Advertisement>>,
+) -> Result<()> {
     let mut adapter_events = adapter.discover_devices().await?;
 
-    while let Some(ev) = adapter_events.next().await {
-        if let bluer::AdapterEvent::DeviceAdded(device_addr) = ev {
-            let device = adapter.device(device_addr)?;
-            let device_name = device.name().await?.unwrap_or("(unknown)".to_string());
-
-            if device_name == target_device_name {
-                let mut device_events = device.events().await?;
-
-                while let Some(device_event) = device_events.next().await {
-                    if let DeviceEvent::PropertyChanged(DeviceProperty::ManufacturerData(md)) =
-                        device_event
-                    {
-                        if let Some(md) = &md.get(&super::VICTRON_MANUFACTURER_ID) {
-                            super::handle_manufacturer_data(
-                                md,
-                                &target_device_encryption_key,
-                                &mut sender,
-                            )?;
+    // Follow every device so that manufacturer data changes and names which are resolved
+    // after the device is added are seen.
+    let mut device_events: StreamMap<Address, DeviceEvents> = StreamMap::new();
+    let mut devices: HashMap<Address, DeviceInfo> = HashMap::new();
+
+    loop {
+        tokio::select! {
+            adapter_event = adapter_events.next() => {
+                match adapter_event {
+                    Some(AdapterEvent::DeviceAdded(device_addr)) => {
+                        if device_events.contains_key(&device_addr) {
+                            continue;
                         }
+
+                        // A device can go away before its properties are read. That only
+                        // affects the device, so skip it rather than ending the scan.
+                        let Ok((info, events)) = follow_device(&adapter, device_addr).await
+                        else {
+                            continue;
+                        };
+
+                        // Send the advertisement BlueZ already holds rather than waiting
+                        // for the next one.
+                        send_advertisements(device_addr, &info, sender)?;
+                        devices.insert(device_addr, info);
+                        device_events.insert(device_addr, events);
                     }
+                    Some(AdapterEvent::DeviceRemoved(device_addr)) => {
+                        devices.remove(&device_addr);
+                        device_events.remove(&device_addr);
+                    }
+                    Some(_) => {}
+                    None => break,
+                }
+            }
+            Some((device_addr, device_event)) = device_events.next() => {
+                let Some(info) = devices.get_mut(&device_addr) else {
+                    continue;
+                };
+                match device_event {
+                    DeviceEvent::PropertyChanged(DeviceProperty::Name(device_name)) => {
+                        info.name = Some(device_name);
+                        send_advertisements(device_addr, info, sender)?;
+                    }
+                    DeviceEvent::PropertyChanged(DeviceProperty::Rssi(rssi)) => {
+                        info.rssi = Some(rssi);
+                    }
+                    DeviceEvent::PropertyChanged(DeviceProperty::ManufacturerData(md)) => {
+                        info.manufacturer_data = md;
+                        send_advertisements(device_addr, info, sender)?;
+                    }
+                    _ => {}
                 }
-
-                return Err(Error::BluetoothEventStreamClosed);
             }
         }
     }
 
     Err(Error::BluetoothEventStreamClosed)
 }
+
+/// Read what is known of a device and subscribe to its property changes.
+async fn follow_device(
+    adapter: &bluer::Adapter,
+    device_addr: Address,
+) -> Result<(DeviceInfo, DeviceEvents)> {
+    let device = adapter.device(device_addr)?;
+    let info = DeviceInfo {
+        name: device.name().await?,
+        rssi: device.rssi().await?,
+        manufacturer_data: device.manufacturer_data().await?.unwrap_or_default(),
+    };
+    let events: DeviceEvents = Box::pin(device.events().await?);
+    Ok((info, events))
+}
+
+fn send_advertisements(
+    device_addr: Address,
+    info: &DeviceInfo,
+    sender: &UnboundedSender<Result<Advertisement>>,
+) -> Result<()> {
+    for (company_id, manufacturer_data) in &info.manufacturer_data {
+        let advertisement = Advertisement {
+            address: device_addr.to_string(),
+            name: info.name.clone(),
+            rssi: info.rssi,
+            company_id: *company_id,
+            manufacturer_data: manufacturer_data.clone(),
+            received_at: SystemTime::now(),
+        };
+        if sender.send(Ok(advertisement)).is_err() {
+            // If consumer has dropped the channel then stop
+            return Err(Error::ClientClosedChannel);
+        }
+    }
+    Ok(())
+}
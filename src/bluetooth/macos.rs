@@ -2,19 +2,40 @@
 
 //! MacOS specific implementation
 
-use crate::{err::*, DeviceState};
+use super::{Advertisement, AdvertisementSource, AdvertisementStream};
+use crate::err::*;
+use std::time::SystemTime;
 use tokio::sync::mpsc::UnboundedSender;
-use tokio_stream::StreamExt;
+use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 
-pub(crate) async fn open_stream(
-    target_device_name: String,
-    target_device_encryption_key: Vec<u8>,
-    mut sender: UnboundedSender<Result<DeviceState>>,
+/// The advertisement source of the operating system, backed by Core Bluetooth.
+#[derive(Debug, Clone, Default)]
+pub struct SystemAdvertisementSource;
+
+impl AdvertisementSource for SystemAdvertisementSource {
+    async fn advertisements(&self) -> Result<AdvertisementStream> {
+        let adapter = bluest::Adapter::default()
+            .await
+            .ok_or(Error::BluetoothAdapterNotFound)?;
+        adapter.wait_available().await?;
+
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            if let Err(e) = scan(adapter, &sender).await {
+                let _ = sender.send(Err(e));
+            }
+        });
+
+        let advertisements: AdvertisementStream = Box::pin(UnboundedReceiverStream::new(receiver));
+        Ok(advertisements)
+    }
+}
+
+async fn scan(
+    adapter: bluest::Adapter,
+    sender: &UnboundedSender<Result<Advertisement>>,
 ) -> Result<()> {
-    let adapter = bluest::Adapter::default()
-        .await
-        .ok_or(Error::BluetoothAdapterNotFound)?;
-    adapter.wait_available().await?;
     let adapter_events_result = adapter.scan(&[]).await;
     let mut adapter_events = match adapter_events_result {
         Ok(adapter_events) => adapter_events,
@@ -25,21 +46,30 @@ pub(crate) async fn open_stream(
     };
 
     while let Some(device) = adapter_events.next().await {
-        let found_device_name = device
+        let Some(md) = device.adv_data.manufacturer_data else {
+            continue;
+        };
+
+        // The name is resolved by the system some time after the device is first seen,
+        // so ask for it on every advertisement.
+        let name = device
             .device
             .name_async()
             .await
-            .unwrap_or("(unknown)".into());
-        if target_device_name == found_device_name {
-            if let Some(md) = device.adv_data.manufacturer_data {
-                if md.company_id == super::VICTRON_MANUFACTURER_ID {
-                    super::handle_manufacturer_data(
-                        &md.data,
-                        &target_device_encryption_key,
-                        &mut sender,
-                    )?;
-                }
-            }
+            .ok()
+            .or(device.adv_data.local_name);
+
+        let advertisement = Advertisement {
+            address: device.device.id().to_string(),
+            name,
+            rssi: device.rssi,
+            company_id: md.company_id,
+            manufacturer_data: md.data,
+            received_at: SystemTime::now(),
+        };
+        if sender.send(Ok(advertisement)).is_err() {
+            // If consumer has dropped the channel then stop
+            return Err(Error::ClientClosedChannel);
         }
     }
 
@@ -1,38 +1,61 @@
 #![cfg(feature = "bluetooth")]
 
+mod discovery;
 mod linux;
 mod macos;
+mod monitor;
+mod source;
+mod supervisor;
 
-use crate::{err::*, DeviceState};
-use tokio::sync::mpsc::UnboundedSender;
-use tokio_stream::{wrappers::UnboundedReceiverStream, Stream};
+use crate::{err::*, DeviceState, EncryptionKey};
+pub use discovery::DiscoveredDevice;
+use discovery::Inventory;
+pub use monitor::*;
+pub use source::*;
+use std::time::Duration;
+use supervisor::supervise_advertisements;
+pub use supervisor::Backoff;
+use tokio_stream::{wrappers::UnboundedReceiverStream, Stream, StreamExt};
 
 #[cfg(target_os = "linux")]
-use linux::open_stream as _open_stream;
+pub use linux::SystemAdvertisementSource;
 #[cfg(target_os = "macos")]
-use macos::open_stream as _open_stream;
+pub use macos::SystemAdvertisementSource;
 
 pub(crate) const VICTRON_MANUFACTURER_ID: u16 = 737;
 
 /// Continuously monitor device state.
 ///
-/// Will attempt to discover the named device, then continuously listen for device state
+/// Will attempt to discover the selected device, then continuously listen for device state
 /// bluetooth broadcasts which will each be decrypted, parsed and sent to the user
 /// via a stream.
 ///
+/// The device is selected by advertised name when given a `String` or `&str`, or by
+/// Bluetooth address (on MacOS, peripheral identifier) when given `DeviceSelector::Address`.
+/// Names are not unique, so select by address when several devices share a name.
+///
+/// Repeats of the same advertisement are dropped, so each item carries new content.
+/// Advertisements whose nonce goes backwards are dropped as possible replays until the device
+/// is taken to have restarted, see `NonceTracker`. The stream ends on the first error; use
+/// `open_stream_supervised` to also be told of possible replays.
+///
+/// To also receive the address, signal strength, receive time and raw manufacturer data of
+/// each update, call `open_multi_stream` with a single descriptor, which yields `Reading`s.
+///
 /// # Example
 ///
 ///  ```rust
 /// # use std::{println, time::Duration};
 /// # use tokio_stream::StreamExt;
+/// # use victron_ble::DeviceSelector;
 /// #
 /// # #[tokio::main]
 /// # async fn main() {
-///     let device_name = "Victron Bluetooth device name".into();
-///     let device_encryption_key = hex::decode("00").unwrap();
+///     let device = DeviceSelector::Address("AA:BB:CC:DD:EE:FF".into());
+///     let device_encryption_key = "00000000000000000000000000000000".parse().unwrap();
 ///
 ///     let mut device_state_stream = victron_ble::open_stream(
-///         device_name,
+///         device,
 ///         device_encryption_key
 ///     ).unwrap();
 ///
@@ -42,13 +65,71 @@ pub(crate) const VICTRON_MANUFACTURER_ID: u16 = 737;
 /// # }
 /// ```
 pub fn open_stream(
-    device_name: String,
-    device_encryption_key: Vec<u8>,
+    device: impl Into<DeviceSelector>,
+    device_encryption_key: EncryptionKey,
+) -> Result<impl Stream<Item = Result<DeviceState>>> {
+    open_stream_with_source(SystemAdvertisementSource, device, device_encryption_key)
+}
+
+/// Continuously monitor device state, as `open_stream` does, receiving advertisements
+/// from the given source.
+pub fn open_stream_with_source(
+    source: impl AdvertisementSource,
+    device: impl Into<DeviceSelector>,
+    device_encryption_key: EncryptionKey,
 ) -> Result<impl Stream<Item = Result<DeviceState>>> {
+    let device = DeviceDescriptor::new(device, device_encryption_key);
+    let reading_stream = open_multi_stream_with_source(source, vec![device])?;
+
+    Ok(reading_stream.map(|result| result.map(|reading| reading.device_state)))
+}
+
+/// Continuously monitor the state of several devices using a single Bluetooth scan.
+///
+/// Each advertisement is matched against the descriptors in order and decrypted with the
+/// key of the first one that matches. The resulting stream yields a `Reading` for
+/// each update, tagged with the descriptor and device it came from. Repeats and possible
+/// replays are handled as described for `open_stream`.
+///
+/// # Example
+///
+///  ```rust
+/// # use std::println;
+/// # use tokio_stream::StreamExt;
+/// # use victron_ble::{DeviceDescriptor, EncryptionKey};
+/// #
+/// # #[tokio::main]
+/// # async fn main() {
+///     let key: EncryptionKey = "00000000000000000000000000000000".parse().unwrap();
+///     let devices = vec![
+///         DeviceDescriptor::by_name("SmartSolar HQ", key.clone()).with_alias("roof"),
+///         DeviceDescriptor::by_address("AA:BB:CC:DD:EE:FF", key).with_alias("shed"),
+///     ];
+///
+///     let mut reading_stream = victron_ble::open_multi_stream(devices).unwrap();
+///
+///     while let Some(result) = reading_stream.next().await {
+///         println!("{result:?}");
+///     }
+/// # }
+/// ```
+pub fn open_multi_stream(
+    devices: Vec<DeviceDescriptor>,
+) -> Result<impl Stream<Item = Result<Reading>>> {
+    open_multi_stream_with_source(SystemAdvertisementSource, devices)
+}
+
+/// Continuously monitor the state of several devices, as `open_multi_stream` does,
+/// receiving advertisements from the given source.
+pub fn open_multi_stream_with_source(
+    source: impl AdvertisementSource,
+    devices: Vec<DeviceDescriptor>,
+) -> Result<impl Stream<Item = Result<Reading>>> {
     let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
 
     tokio::spawn(async move {
-        if let Err(e) = _open_stream(device_name, device_encryption_key, sender.clone()).await {
+        let mut monitor = Monitor::new(devices, sender.clone());
+        if let Err(e) = monitor_advertisements(&source, &mut monitor).await {
             let _ = sender.send(Err(e));
         }
     });
@@ -56,26 +137,433 @@ pub fn open_stream(
     Ok(UnboundedReceiverStream::new(receiver))
 }
 
-fn handle_manufacturer_data(
-    manufacturer_data: &[u8],
-    device_encryption_key: &[u8],
-    sender: &mut UnboundedSender<Result<DeviceState>>,
+/// Continuously monitor device state, as `open_stream` does, but keep going through errors
+/// that are not fatal.
+///
+/// Transient errors, such as a corrupt advertisement, are reported as items and the stream
+/// continues. Possible replays are reported as `Error::PossibleReplay`. An incorrect key is
+/// fatal, so select devices by address where another device shares the name. If the Bluetooth adapter fails or its event stream ends, the error is reported
+/// and the adapter is acquired again after the delay given by `backoff`. The stream only ends
+/// on a fatal error, see `Error::is_transient`, or when `backoff.max_attempts` is exceeded.
+pub fn open_stream_supervised(
+    device: impl Into<DeviceSelector>,
+    device_encryption_key: EncryptionKey,
+    backoff: Backoff,
+) -> Result<impl Stream<Item = Result<DeviceState>>> {
+    let device = DeviceDescriptor::new(device, device_encryption_key);
+    let reading_stream = open_multi_stream_supervised(vec![device], backoff)?;
+
+    Ok(reading_stream.map(|result| result.map(|reading| reading.device_state)))
+}
+
+/// Continuously monitor the state of several devices, as `open_multi_stream` does, but keep
+/// going through errors that are not fatal as described for `open_stream_supervised`.
+pub fn open_multi_stream_supervised(
+    devices: Vec<DeviceDescriptor>,
+    backoff: Backoff,
+) -> Result<impl Stream<Item = Result<Reading>>> {
+    open_multi_stream_supervised_with_source(SystemAdvertisementSource, devices, backoff)
+}
+
+/// Continuously monitor the state of several devices, as `open_multi_stream_supervised` does,
+/// receiving advertisements from the given source.
+pub fn open_multi_stream_supervised_with_source(
+    source: impl AdvertisementSource,
+    devices: Vec<DeviceDescriptor>,
+    backoff: Backoff,
+) -> Result<impl Stream<Item = Result<Reading>>> {
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        let monitor = Monitor::new(devices, sender.clone()).supervised();
+        if let Err(e) = supervise_advertisements(&source, monitor, &backoff).await {
+            let _ = sender.send(Err(e));
+        }
+    });
+
+    Ok(UnboundedReceiverStream::new(receiver))
+}
+
+async fn monitor_advertisements(
+    source: &impl AdvertisementSource,
+    monitor: &mut Monitor,
 ) -> Result<()> {
-    let device_state_result =
-        super::parse_manufacturer_data(manufacturer_data, device_encryption_key);
-
-    match device_state_result {
-        Err(Error::WrongAdvertisement) => Ok(()), // Message irrelevant to user, wait for next advertisement
-        Err(e) => {
-            // Fatal error, stop
-            Err(e)
+    let mut advertisements = source.advertisements().await?;
+
+    while let Some(advertisement) = advertisements.next().await {
+        let advertisement = advertisement?;
+        if advertisement.company_id != VICTRON_MANUFACTURER_ID {
+            continue;
+        }
+
+        let index = monitor.find(&advertisement.address, advertisement.name.as_deref());
+        if let Some(index) = index {
+            monitor.handle_advertisement(index, advertisement)?;
         }
-        Ok(device_state) => {
-            if sender.send(Ok(device_state)).is_err() {
-                // If consumer has dropped the channel then stop
-                return Err(Error::ClientClosedChannel);
+    }
+
+    Err(Error::BluetoothEventStreamClosed)
+}
+
+/// List the Victron devices advertising nearby.
+///
+/// Scans for the given duration and returns every device that published manufacturer data
+/// under the Victron company ID, ordered by address. No encryption key is needed since only
+/// the unencrypted header is read. Names that are resolved during the scan are included,
+/// even if the device was first seen without one.
+///
+/// # Example
+///
+///  ```rust
+/// # use std::{println, time::Duration};
+/// #
+/// # #[tokio::main]
+/// # async fn main() {
+///     if let Ok(devices) = victron_ble::discover(Duration::from_secs(10)).await {
+///         for device in devices {
+///             println!("{} {:?} {:?}", device.address, device.name, device.product());
+///         }
+///     }
+/// # }
+/// ```
+pub async fn discover(duration: Duration) -> Result<Vec<DiscoveredDevice>> {
+    discover_with_source(&SystemAdvertisementSource, duration).await
+}
+
+/// List the Victron devices advertising nearby, as `discover` does, receiving
+/// advertisements from the given source.
+pub async fn discover_with_source(
+    source: &impl AdvertisementSource,
+    duration: Duration,
+) -> Result<Vec<DiscoveredDevice>> {
+    let mut advertisements = source.advertisements().await?;
+    let mut inventory = Inventory::default();
+
+    let deadline = tokio::time::sleep(duration);
+    tokio::pin!(deadline);
+
+    loop {
+        tokio::select! {
+            _ = &mut deadline => break,
+            advertisement = advertisements.next() => {
+                // A source that ends before the deadline has nothing more to report.
+                let Some(advertisement) = advertisement else {
+                    break;
+                };
+                let advertisement = advertisement?;
+                if advertisement.company_id == VICTRON_MANUFACTURER_ID {
+                    inventory.observe(advertisement);
+                }
             }
-            Ok(())
         }
     }
+
+    Ok(inventory.into_devices())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ErrorState, Mode, SolarChargerState};
+    use std::{collections::VecDeque, sync::Mutex};
+
+    /// Replays a fixed list of advertisements, then ends.
+    struct ScriptedSource(Vec<Advertisement>);
+
+    impl AdvertisementSource for ScriptedSource {
+        async fn advertisements(&self) -> Result<AdvertisementStream> {
+            let advertisements = self.0.clone().into_iter().map(Ok);
+            Ok(Box::pin(tokio_stream::iter(advertisements)))
+        }
+    }
+
+    /// Replays one scan from the script each time it is acquired, then fails to find an
+    /// adapter once the script is used up.
+    struct FlakySource(Mutex<VecDeque<Result<Vec<Advertisement>>>>);
+
+    impl AdvertisementSource for FlakySource {
+        async fn advertisements(&self) -> Result<AdvertisementStream> {
+            let scan = self.0.lock().unwrap().pop_front();
+            let advertisements = scan.unwrap_or(Err(Error::BluetoothAdapterNotFound))?;
+            Ok(Box::pin(tokio_stream::iter(
+                advertisements.into_iter().map(Ok),
+            )))
+        }
+    }
+
+    fn key() -> EncryptionKey {
+        EncryptionKey::from_hex("0df4d0395b7d1a876c0c33ecb9e70dcd").unwrap()
+    }
+
+    fn device_state() -> DeviceState {
+        DeviceState::SolarCharger(SolarChargerState {
+            mode: Mode::Absorption,
+            error_state: ErrorState::NoError,
+            battery_voltage_v: Some(14.2),
+            battery_current_a: Some(3.0),
+            yield_today_kwh: Some(0.75),
+            pv_power_w: Some(45.0),
+            load_current_a: None,
+        })
+    }
+
+    fn advertisement(address: &str, name: Option<&str>, nonce: u16) -> Advertisement {
+        advertisement_with_key(address, name, nonce, &key())
+    }
+
+    fn advertisement_with_key(
+        address: &str,
+        name: Option<&str>,
+        nonce: u16,
+        key: &EncryptionKey,
+    ) -> Advertisement {
+        let manufacturer_data =
+            crate::encode_manufacturer_data(&device_state(), 0xA055, nonce, key).unwrap();
+        Advertisement {
+            address: address.into(),
+            name: name.map(Into::into),
+            rssi: Some(-60),
+            company_id: VICTRON_MANUFACTURER_ID,
+            manufacturer_data: manufacturer_data.into(),
+            received_at: std::time::SystemTime::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_open_multi_stream_with_source() {
+        let source = ScriptedSource(vec![
+            advertisement("AA:AA:AA:AA:AA:AA", None, 1),
+            advertisement("AA:AA:AA:AA:AA:AA", Some("SmartSolar HQ"), 1),
+            advertisement("BB:BB:BB:BB:BB:BB", Some("SmartSolar HQ"), 7),
+            advertisement("AA:AA:AA:AA:AA:AA", Some("SmartSolar HQ"), 1),
+            Advertisement {
+                company_id: 0x004C,
+                ..advertisement("BB:BB:BB:BB:BB:BB", None, 8)
+            },
+        ]);
+        let devices = vec![
+            DeviceDescriptor::by_address("bb:bb:bb:bb:bb:bb", key()).with_alias("shed"),
+            DeviceDescriptor::by_name("SmartSolar HQ", key()),
+        ];
+
+        let results: Vec<_> = open_multi_stream_with_source(source, devices)
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 3);
+        let reading = results[0].as_ref().unwrap();
+        assert_eq!(reading.index, 1);
+        assert_eq!(reading.address, "AA:AA:AA:AA:AA:AA");
+        assert_eq!(reading.device_state, device_state());
+        let reading = results[1].as_ref().unwrap();
+        assert_eq!((reading.index, reading.alias.as_deref()), (0, Some("shed")));
+        assert!(matches!(results[2], Err(Error::BluetoothEventStreamClosed)));
+    }
+
+    #[tokio::test]
+    async fn test_open_multi_stream_with_source_reading() {
+        let received_at = std::time::SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let advertisement = Advertisement {
+            rssi: Some(-81),
+            received_at,
+            ..advertisement("AA:AA:AA:AA:AA:AA", Some("SmartSolar HQ"), 0x0102)
+        };
+        let source = ScriptedSource(vec![advertisement.clone()]);
+        let devices = vec![DeviceDescriptor::by_name("SmartSolar HQ", key())];
+
+        let results: Vec<_> = open_multi_stream_with_source(source, devices)
+            .unwrap()
+            .collect()
+            .await;
+
+        let reading = results[0].as_ref().unwrap();
+        assert_eq!(reading.name.as_deref(), Some("SmartSolar HQ"));
+        assert_eq!(reading.rssi, Some(-81));
+        assert_eq!(reading.received_at, received_at);
+        assert_eq!(reading.header.product_id, 0xA055);
+        assert_eq!(reading.product().unwrap().name, "SmartSolar MPPT 100/15");
+        assert_eq!(reading.header.nonce, 0x0102);
+        assert_eq!(reading.manufacturer_data, advertisement.manufacturer_data);
+        assert_eq!(reading.device_state, device_state());
+    }
+
+    #[tokio::test]
+    async fn test_open_stream_with_source_by_address() {
+        let other_key = EncryptionKey::new([0xAA; 16]);
+        let source = ScriptedSource(vec![
+            advertisement_with_key("BB:BB:BB:BB:BB:BB", None, 1, &other_key),
+            advertisement("AA:AA:AA:AA:AA:AA", None, 1),
+        ]);
+        let device = DeviceSelector::Address("aa:aa:aa:aa:aa:aa".into());
+
+        let results: Vec<_> = open_stream_with_source(source, device, key())
+            .unwrap()
+            .collect()
+            .await;
+
+        // The name is never resolved, and the other device is not decoded with the key.
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), &device_state());
+        assert!(matches!(results[1], Err(Error::BluetoothEventStreamClosed)));
+    }
+
+    #[tokio::test]
+    async fn test_open_stream_with_source_by_address_no_match() {
+        let source = ScriptedSource(vec![
+            advertisement("AA:AA:AA:AA:AA:AA", Some("SmartSolar HQ"), 1),
+            advertisement("BB:BB:BB:BB:BB:BB", None, 1),
+        ]);
+        let device = DeviceSelector::Address("CC:CC:CC:CC:CC:CC".into());
+
+        let results: Vec<_> = open_stream_with_source(source, device, key())
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], Err(Error::BluetoothEventStreamClosed)));
+    }
+
+    #[tokio::test]
+    async fn test_open_stream_with_source_drops_possible_replays() {
+        let source = ScriptedSource(vec![
+            advertisement("AA:AA:AA:AA:AA:AA", None, 5),
+            advertisement("AA:AA:AA:AA:AA:AA", None, 3),
+            advertisement("AA:AA:AA:AA:AA:AA", None, 6),
+        ]);
+        let device = DeviceSelector::Address("AA:AA:AA:AA:AA:AA".into());
+
+        let results: Vec<_> = open_stream_with_source(source, device, key())
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok() && results[1].is_ok());
+        assert!(matches!(results[2], Err(Error::BluetoothEventStreamClosed)));
+    }
+
+    #[tokio::test]
+    async fn test_open_multi_stream_supervised_reports_possible_replays() {
+        let source = FlakySource(Mutex::new(VecDeque::from([Ok(vec![
+            advertisement("AA:AA:AA:AA:AA:AA", None, 5),
+            advertisement("AA:AA:AA:AA:AA:AA", None, 3),
+            advertisement("AA:AA:AA:AA:AA:AA", None, 6),
+        ])])));
+        let devices = vec![DeviceDescriptor::by_address("AA:AA:AA:AA:AA:AA", key())];
+        let backoff = Backoff {
+            max_attempts: Some(0),
+            ..Backoff::default()
+        };
+
+        let results: Vec<_> = open_multi_stream_supervised_with_source(source, devices, backoff)
+            .unwrap()
+            .collect()
+            .await;
+
+        let errors: Vec<_> = results.iter().map(|result| result.as_ref().err()).collect();
+        assert!(matches!(
+            errors[..],
+            [
+                None,
+                Some(Error::PossibleReplay {
+                    nonce: 3,
+                    last_nonce: 5
+                }),
+                None,
+                Some(Error::BluetoothEventStreamClosed),
+            ]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_discover_with_source() {
+        let source = ScriptedSource(vec![
+            advertisement("BB:BB:BB:BB:BB:BB", None, 1),
+            advertisement("AA:AA:AA:AA:AA:AA", Some("SmartSolar HQ"), 1),
+        ]);
+
+        let devices = discover_with_source(&source, Duration::from_secs(10))
+            .await
+            .unwrap();
+
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].name.as_deref(), Some("SmartSolar HQ"));
+        assert_eq!(devices[1].product_id, Some(0xA055));
+    }
+
+    #[tokio::test]
+    async fn test_open_multi_stream_supervised_with_source() {
+        let mut too_long = advertisement("BB:BB:BB:BB:BB:BB", Some("SmartSolar HQ"), 1);
+        too_long.manufacturer_data.push(0);
+        let source = FlakySource(Mutex::new(VecDeque::from([
+            Ok(vec![
+                advertisement("AA:AA:AA:AA:AA:AA", Some("SmartSolar HQ"), 1),
+                too_long,
+            ]),
+            Err(Error::BluetoothAdapterNotFound),
+            Ok(vec![
+                advertisement("AA:AA:AA:AA:AA:AA", Some("SmartSolar HQ"), 1),
+                advertisement("AA:AA:AA:AA:AA:AA", Some("SmartSolar HQ"), 2),
+            ]),
+        ])));
+        let devices = vec![DeviceDescriptor::by_name("SmartSolar HQ", key())];
+        let backoff = Backoff {
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            multiplier: 2,
+            max_attempts: Some(2),
+        };
+
+        let results: Vec<_> = open_multi_stream_supervised_with_source(source, devices, backoff)
+            .unwrap()
+            .collect()
+            .await;
+
+        let errors: Vec<_> = results.iter().map(|result| result.as_ref().err()).collect();
+        assert!(matches!(
+            errors[..],
+            [
+                None,
+                Some(Error::RecordTooBig),
+                Some(Error::BluetoothEventStreamClosed),
+                Some(Error::BluetoothAdapterNotFound),
+                None,
+                Some(Error::BluetoothEventStreamClosed),
+                Some(Error::BluetoothAdapterNotFound),
+                Some(Error::BluetoothAdapterNotFound),
+            ]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_open_multi_stream_supervised_ends_on_incorrect_key() {
+        let other_key = EncryptionKey::new([0xAA; 16]);
+        let source = FlakySource(Mutex::new(VecDeque::from([
+            Ok(vec![
+                advertisement("AA:AA:AA:AA:AA:AA", Some("SmartSolar HQ"), 1),
+                advertisement_with_key("BB:BB:BB:BB:BB:BB", Some("SmartSolar HQ"), 1, &other_key),
+            ]),
+            Ok(vec![advertisement(
+                "AA:AA:AA:AA:AA:AA",
+                Some("SmartSolar HQ"),
+                2,
+            )]),
+        ])));
+        let devices = vec![DeviceDescriptor::by_name("SmartSolar HQ", key())];
+
+        let results: Vec<_> =
+            open_multi_stream_supervised_with_source(source, devices, Backoff::default())
+                .unwrap()
+                .collect()
+                .await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(Error::IncorrectDeviceEncryptionKey)
+        ));
+    }
 }
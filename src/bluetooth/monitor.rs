@@ -0,0 +1,297 @@
+use super::Advertisement;
+use crate::{
+    err::*, AdvertisementHeader, Decoder, DeviceState, EncryptionKey, NonceStatus, NonceTracker,
+    Product,
+};
+use std::time::SystemTime;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Identifies a device to monitor.
+///
+/// Names are not unique and are only known once the device has been resolved, so prefer
+/// selecting a device by address where it is known. A `String` or `&str` converts into
+/// `DeviceSelector::Name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceSelector {
+    /// Match devices by their advertised name.
+    Name(String),
+    /// Match a device by its Bluetooth address, such as "AA:BB:CC:DD:EE:FF", or on MacOS by
+    /// its peripheral identifier. Matching is case insensitive.
+    Address(String),
+}
+
+impl From<String> for DeviceSelector {
+    fn from(name: String) -> Self {
+        Self::Name(name)
+    }
+}
+
+impl From<&str> for DeviceSelector {
+    fn from(name: &str) -> Self {
+        Self::Name(name.into())
+    }
+}
+
+impl DeviceSelector {
+    pub(crate) fn matches(&self, address: &str, name: Option<&str>) -> bool {
+        match self {
+            Self::Name(target_name) => name == Some(target_name.as_str()),
+            Self::Address(target_address) => target_address.eq_ignore_ascii_case(address),
+        }
+    }
+}
+
+/// A device to monitor, with the key used to decrypt its advertisements.
+#[derive(Debug, Clone)]
+pub struct DeviceDescriptor {
+    pub selector: DeviceSelector,
+    pub device_encryption_key: EncryptionKey,
+    /// A name of your choosing that is passed through to each `Reading`.
+    pub alias: Option<String>,
+}
+
+impl DeviceDescriptor {
+    pub fn new(selector: impl Into<DeviceSelector>, device_encryption_key: EncryptionKey) -> Self {
+        Self {
+            selector: selector.into(),
+            device_encryption_key,
+            alias: None,
+        }
+    }
+
+    pub fn by_name(name: impl Into<String>, device_encryption_key: EncryptionKey) -> Self {
+        Self::new(DeviceSelector::Name(name.into()), device_encryption_key)
+    }
+
+    pub fn by_address(address: impl Into<String>, device_encryption_key: EncryptionKey) -> Self {
+        let selector = DeviceSelector::Address(address.into());
+        Self::new(selector, device_encryption_key)
+    }
+
+    pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+        self.alias = Some(alias.into());
+        self
+    }
+}
+
+/// A device state received from one of the devices passed to `open_multi_stream`, together
+/// with where and when it was received and the advertisement it was decoded from.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    /// The position of the matching descriptor in the list passed to `open_multi_stream`.
+    pub index: usize,
+    /// The alias of the matching descriptor.
+    pub alias: Option<String>,
+    /// The Bluetooth address of the device. On MacOS this is the peripheral identifier.
+    pub address: String,
+    /// The advertised name of the device, if it has been resolved.
+    pub name: Option<String>,
+    /// The signal strength in dBm, if known.
+    pub rssi: Option<i16>,
+    /// When the advertisement was received.
+    pub received_at: SystemTime,
+    /// The unencrypted header, giving the product ID and nonce of the advertisement.
+    pub header: AdvertisementHeader,
+    /// The manufacturer data as received, before decryption.
+    pub manufacturer_data: Vec<u8>,
+    pub device_state: DeviceState,
+}
+
+impl Reading {
+    /// Look up the product that sent the advertisement from the catalogue of known Victron
+    /// products.
+    pub fn product(&self) -> Option<&'static Product> {
+        self.header.product()
+    }
+}
+
+/// The state shared by the platform backends while monitoring a set of devices.
+pub(crate) struct Monitor {
+    devices: Vec<(DeviceDescriptor, Decoder)>,
+    nonce_tracker: NonceTracker<String>,
+    sender: UnboundedSender<Result<Reading>>,
+    /// Whether transient errors are reported to the user rather than ending the stream.
+    supervised: bool,
+    readings_sent: u64,
+}
+
+impl Monitor {
+    pub(crate) fn new(
+        devices: Vec<DeviceDescriptor>,
+        sender: UnboundedSender<Result<Reading>>,
+    ) -> Self {
+        Self {
+            devices: devices
+                .into_iter()
+                .map(|device| {
+                    let decoder = Decoder::new(&device.device_encryption_key);
+                    (device, decoder)
+                })
+                .collect(),
+            nonce_tracker: NonceTracker::new(),
+            sender,
+            supervised: false,
+            readings_sent: 0,
+        }
+    }
+
+    pub(crate) fn supervised(mut self) -> Self {
+        self.supervised = true;
+        self
+    }
+
+    /// The number of readings sent so far, used to tell whether a scan made progress.
+    pub(crate) fn readings_sent(&self) -> u64 {
+        self.readings_sent
+    }
+
+    /// The index of the first descriptor that matches the device with the given address and name.
+    pub(crate) fn find(&self, address: &str, name: Option<&str>) -> Option<usize> {
+        self.devices
+            .iter()
+            .position(|(device, _)| device.selector.matches(address, name))
+    }
+
+    /// Decode an advertisement from the device matching the descriptor with the given index.
+    pub(crate) fn handle_advertisement(
+        &mut self,
+        index: usize,
+        advertisement: Advertisement,
+    ) -> Result<()> {
+        let header = match AdvertisementHeader::parse(&advertisement.manufacturer_data) {
+            Ok(header) => header,
+            Err(e) => return self.handle_error(e),
+        };
+
+        let (device, decoder) = &self.devices[index];
+        let address = &advertisement.address;
+        let device_state_result = match self.nonce_tracker.observe(address.clone(), header.nonce) {
+            NonceStatus::Repeat => return Ok(()), // Content unchanged since the last advertisement
+            NonceStatus::PossibleReplay { last_nonce } => Err(Error::PossibleReplay {
+                nonce: header.nonce,
+                last_nonce,
+            }),
+            _ => decoder.decode(&advertisement.manufacturer_data),
+        };
+
+        match device_state_result {
+            Err(e) => self.handle_error(e),
+            Ok(device_state) => {
+                let reading = Reading {
+                    index,
+                    alias: device.alias.clone(),
+                    address: advertisement.address,
+                    name: advertisement.name,
+                    rssi: advertisement.rssi,
+                    received_at: advertisement.received_at,
+                    header,
+                    manufacturer_data: advertisement.manufacturer_data,
+                    device_state,
+                };
+                self.readings_sent += 1;
+                self.send(Ok(reading))
+            }
+        }
+    }
+
+    fn handle_error(&self, e: Error) -> Result<()> {
+        match e {
+            Error::WrongAdvertisement => Ok(()), // Message irrelevant to user, wait for next advertisement
+            // Only supervised streams report errors without ending, so elsewhere drop it
+            Error::PossibleReplay { .. } if !self.supervised => Ok(()),
+            e if self.supervised && e.is_transient() => self.send(Err(e)),
+            e => {
+                // Fatal error, stop
+                Err(e)
+            }
+        }
+    }
+
+    pub(crate) fn send(&self, result: Result<Reading>) -> Result<()> {
+        if self.sender.send(result).is_err() {
+            // If consumer has dropped the channel then stop
+            return Err(Error::ClientClosedChannel);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ErrorState, Mode, SolarChargerState};
+
+    fn key(first: u8) -> EncryptionKey {
+        EncryptionKey::new([first; 16])
+    }
+
+    fn advertisement(address: &str, key: &EncryptionKey, nonce: u16) -> Advertisement {
+        let device_state = DeviceState::SolarCharger(SolarChargerState {
+            mode: Mode::Float,
+            error_state: ErrorState::NoError,
+            battery_voltage_v: Some(13.5),
+            battery_current_a: Some(1.0),
+            yield_today_kwh: Some(0.5),
+            pv_power_w: Some(20.0),
+            load_current_a: None,
+        });
+        let manufacturer_data =
+            crate::encode_manufacturer_data(&device_state, 0xA055, nonce, key).unwrap();
+        Advertisement {
+            address: address.into(),
+            name: None,
+            rssi: Some(-72),
+            company_id: super::super::VICTRON_MANUFACTURER_ID,
+            manufacturer_data: manufacturer_data.into(),
+            received_at: SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn test_device_selector_matches() {
+        let by_name = DeviceSelector::Name("SmartSolar HQ".into());
+        let by_address = DeviceSelector::Address("aa:bb:cc:dd:ee:ff".into());
+
+        assert!(by_name.matches("AA:BB:CC:DD:EE:FF", Some("SmartSolar HQ")));
+        assert!(!by_name.matches("AA:BB:CC:DD:EE:FF", None));
+        assert!(by_address.matches("AA:BB:CC:DD:EE:FF", None));
+        assert!(!by_address.matches("AA:BB:CC:DD:EE:00", Some("SmartSolar HQ")));
+        assert_eq!(DeviceSelector::from("SmartSolar HQ"), by_name);
+    }
+
+    #[test]
+    fn test_monitor_handle_manufacturer_data() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let mut monitor = Monitor::new(
+            vec![
+                DeviceDescriptor::by_name("Charger", key(1)).with_alias("roof"),
+                DeviceDescriptor::by_address("AA:BB:CC:DD:EE:FF", key(2)),
+            ],
+            sender,
+        );
+
+        let index = monitor.find("AA:BB:CC:DD:EE:FF", Some("Other")).unwrap();
+        let first = advertisement("AA:BB:CC:DD:EE:FF", &key(2), 1);
+        monitor.handle_advertisement(index, first.clone()).unwrap();
+        monitor.handle_advertisement(index, first.clone()).unwrap();
+        let index = monitor.find("11:22:33:44:55:66", Some("Charger")).unwrap();
+        let second = advertisement("11:22:33:44:55:66", &key(1), 0x0102);
+        monitor.handle_advertisement(index, second).unwrap();
+
+        let reading = receiver.try_recv().unwrap().unwrap();
+        assert_eq!((reading.index, reading.alias), (1, None));
+        assert_eq!(reading.manufacturer_data, first.manufacturer_data);
+        assert_eq!(reading.rssi, Some(-72));
+        assert_eq!(reading.received_at, SystemTime::UNIX_EPOCH);
+        let reading = receiver.try_recv().unwrap().unwrap();
+        assert_eq!((reading.index, reading.alias), (0, Some("roof".into())));
+        assert_eq!(reading.address, "11:22:33:44:55:66");
+        assert_eq!(
+            (reading.header.product_id, reading.header.nonce),
+            (0xA055, 0x0102)
+        );
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(monitor.find("11:22:33:44:55:66", None), None);
+    }
+}
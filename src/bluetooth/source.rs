@@ -0,0 +1,38 @@
+use crate::err::*;
+use core::{future::Future, pin::Pin};
+use std::time::SystemTime;
+use tokio_stream::Stream;
+
+/// Manufacturer data received from a Bluetooth device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Advertisement {
+    /// The Bluetooth address of the device. On MacOS this is the peripheral identifier.
+    pub address: String,
+    /// The advertised name of the device, if it has been resolved.
+    pub name: Option<String>,
+    /// The signal strength in dBm, if known.
+    pub rssi: Option<i16>,
+    /// The Bluetooth SIG company ID that the manufacturer data was published under.
+    pub company_id: u16,
+    pub manufacturer_data: Vec<u8>,
+    /// When the advertisement was received.
+    pub received_at: SystemTime,
+}
+
+/// A stream of advertisements produced by an `AdvertisementSource`.
+pub type AdvertisementStream = Pin<Box<dyn Stream<Item = Result<Advertisement>> + Send>>;
+
+/// Produces the advertisements received by a Bluetooth stack.
+///
+/// `open_stream`, `open_multi_stream` and `discover` use `SystemAdvertisementSource`,
+/// which is backed by BlueZ on Linux and Core Bluetooth on MacOS. Implement this trait to
+/// plug in another Bluetooth stack, or to replay scripted advertisements in tests, and pass
+/// it to the `_with_source` variants of those functions.
+///
+/// An advertisement should be produced whenever the manufacturer data of a device changes,
+/// and again when its name is resolved so that devices selected by name can be matched.
+/// Producing the same manufacturer data again is harmless since repeats are dropped.
+pub trait AdvertisementSource: Send + Sync + 'static {
+    /// Start scanning. An error in the returned stream, or the stream ending, ends the scan.
+    fn advertisements(&self) -> impl Future<Output = Result<AdvertisementStream>> + Send;
+}
@@ -0,0 +1,94 @@
+use super::{monitor_advertisements, AdvertisementSource, Monitor};
+use crate::err::*;
+use std::time::Duration;
+
+/// How a supervised stream waits before acquiring the Bluetooth adapter again.
+///
+/// The delay starts at `initial_delay` and is multiplied by `multiplier` after each
+/// consecutive failure, up to `max_delay`. It is reset once a scan produces a reading.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backoff {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: u32,
+    /// The number of consecutive failures after which the stream gives up, or `None` to
+    /// keep trying forever.
+    pub max_attempts: Option<u32>,
+}
+
+impl Backoff {
+    /// The delay before the given attempt to acquire the adapter again, counting from 1.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .checked_pow(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        self.initial_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            multiplier: 2,
+            max_attempts: None,
+        }
+    }
+}
+
+/// Monitor the advertisements of the source, acquiring it again whenever it fails with a
+/// transient error. Each failure is reported to the user before waiting.
+pub(crate) async fn supervise_advertisements(
+    source: &impl AdvertisementSource,
+    mut monitor: Monitor,
+    backoff: &Backoff,
+) -> Result<()> {
+    let mut failures = 0;
+
+    loop {
+        let readings_sent = monitor.readings_sent();
+        let Err(e) = monitor_advertisements(source, &mut monitor).await else {
+            return Ok(());
+        };
+        if !e.is_transient() {
+            return Err(e);
+        }
+
+        if monitor.readings_sent() != readings_sent {
+            failures = 0;
+        }
+        failures += 1;
+        if backoff.max_attempts.is_some_and(|max| failures > max) {
+            return Err(e);
+        }
+
+        monitor.send(Err(e))?;
+        tokio::time::sleep(backoff.delay(failures)).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay() {
+        let backoff = Backoff {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(3),
+            multiplier: 2,
+            max_attempts: None,
+        };
+
+        assert_eq!(backoff.delay(1), Duration::from_millis(500));
+        assert_eq!(backoff.delay(2), Duration::from_secs(1));
+        assert_eq!(backoff.delay(3), Duration::from_secs(2));
+        assert_eq!(backoff.delay(4), Duration::from_secs(3));
+        assert_eq!(backoff.delay(100), Duration::from_secs(3));
+    }
+}
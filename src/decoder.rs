@@ -0,0 +1,161 @@
+use crate::encryption_key::EncryptionKey;
+use crate::err::*;
+use crate::model::{AdvertisementHeader, DeviceState};
+use crate::record::{self, Record};
+use aes::cipher::KeyInit;
+use core::fmt;
+
+/// Decrypts and parses the manufacturer data published by a device with a known key.
+///
+/// The AES key schedule is computed once when the decoder is created instead of for
+/// every advertisement, so a gateway handling a high rate of advertisements should keep
+/// one decoder per device rather than calling `parse_manufacturer_data` each time.
+/// Decoding does not allocate.
+#[derive(Clone)]
+pub struct Decoder {
+    key: EncryptionKey,
+    cipher: aes::Aes128,
+}
+
+impl Decoder {
+    pub fn new(device_encryption_key: &EncryptionKey) -> Self {
+        Self {
+            key: device_encryption_key.clone(),
+            cipher: aes::Aes128::new(device_encryption_key.as_bytes().into()),
+        }
+    }
+
+    /// Decrypt and parse the content of the manufacturer data.
+    pub fn decode(&self, manufacturer_data: &[u8]) -> Result<DeviceState> {
+        let record = Record::new(manufacturer_data, self)?;
+        DeviceState::parse(&record)
+    }
+
+    /// Decrypt and parse the content of the manufacturer data, also returning the
+    /// unencrypted header which identifies the product that sent it.
+    pub fn decode_with_header(
+        &self,
+        manufacturer_data: &[u8],
+    ) -> Result<(AdvertisementHeader, DeviceState)> {
+        let record = Record::new(manufacturer_data, self)?;
+        let device_state = DeviceState::parse(&record)?;
+        Ok((*record.header(), device_state))
+    }
+
+    /// Decrypt the payload of the manufacturer data into the given buffer without parsing
+    /// it, returning the unencrypted header. Useful for forwarding the raw payload or
+    /// parsing it later.
+    pub fn decrypt_into(
+        &self,
+        manufacturer_data: &[u8],
+        payload: &mut [u8; 16],
+    ) -> Result<AdvertisementHeader> {
+        let record = Record::new(manufacturer_data, self)?;
+        record.decrypt_into(payload)?;
+        Ok(*record.header())
+    }
+
+    /// Encode and encrypt a device state into manufacturer data, as
+    /// `encode_manufacturer_data` does.
+    pub fn encode(
+        &self,
+        device_state: &DeviceState,
+        product_id: u16,
+        nonce: u16,
+    ) -> Result<[u8; 24]> {
+        let header = AdvertisementHeader {
+            product_id,
+            record_type: device_state.record_type(),
+            nonce,
+            key_check: self.key.key_check(),
+        };
+        record::encrypt(&header, &device_state.encode()?, self)
+    }
+
+    pub(crate) fn key(&self) -> &EncryptionKey {
+        &self.key
+    }
+
+    pub(crate) fn cipher(&self) -> &aes::Aes128 {
+        &self.cipher
+    }
+}
+
+impl From<EncryptionKey> for Decoder {
+    fn from(device_encryption_key: EncryptionKey) -> Self {
+        Self::new(&device_encryption_key)
+    }
+}
+
+impl fmt::Debug for Decoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Decoder")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::{ErrorState, Mode, SolarChargerState};
+
+    fn key() -> EncryptionKey {
+        EncryptionKey::from_hex("0df4d0395b7d1a876c0c33ecb9e70dcd").unwrap()
+    }
+
+    fn device_state() -> DeviceState {
+        DeviceState::SolarCharger(SolarChargerState {
+            mode: Mode::Bulk,
+            error_state: ErrorState::NoError,
+            battery_voltage_v: Some(13.2),
+            battery_current_a: Some(5.0),
+            yield_today_kwh: Some(1.25),
+            pv_power_w: Some(72.0),
+            load_current_a: None,
+        })
+    }
+
+    #[test]
+    fn test_decoder_decode() {
+        let decoder = Decoder::new(&key());
+        let manufacturer_data = decoder.encode(&device_state(), 0xA055, 0x2221).unwrap();
+
+        let (header, result) = decoder.decode_with_header(&manufacturer_data).unwrap();
+
+        assert_eq!(result, device_state());
+        assert_eq!(header.product_id, 0xA055);
+        assert_eq!(
+            crate::parse_manufacturer_data(&manufacturer_data, &key()).unwrap(),
+            result
+        );
+        assert_eq!(decoder.decode(&manufacturer_data).unwrap(), result);
+    }
+
+    #[test]
+    fn test_decoder_decrypt_into() {
+        let decoder = Decoder::from(key());
+        let manufacturer_data = decoder.encode(&device_state(), 0xA055, 0x2221).unwrap();
+        let mut payload = [0; 16];
+
+        let header = decoder
+            .decrypt_into(&manufacturer_data, &mut payload)
+            .unwrap();
+
+        assert_eq!(header.nonce, 0x2221);
+        assert_eq!(payload, device_state().encode().unwrap());
+    }
+
+    #[test]
+    fn test_decoder_incorrect_key() {
+        let decoder = Decoder::new(&EncryptionKey::new([0xAA; 16]));
+        let manufacturer_data = Decoder::new(&key())
+            .encode(&device_state(), 0xA055, 0x2221)
+            .unwrap();
+
+        assert!(matches!(
+            decoder.decode(&manufacturer_data),
+            Err(Error::IncorrectDeviceEncryptionKey)
+        ));
+    }
+}
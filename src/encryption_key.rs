@@ -0,0 +1,148 @@
+use crate::err::*;
+use core::{fmt, str::FromStr};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// The length in bytes of a Victron device encryption key.
+pub const ENCRYPTION_KEY_LEN: usize = 16;
+
+/// The key used to decrypt the state broadcasts of a Victron device.
+///
+/// It is shown in the Victron Connect app as 32 hexadecimal characters and can be
+/// parsed from that form with `EncryptionKey::from_hex` or `str::parse`. The key is
+/// redacted in `Debug` output and zeroized when dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey([u8; ENCRYPTION_KEY_LEN]);
+
+impl EncryptionKey {
+    pub fn new(bytes: [u8; ENCRYPTION_KEY_LEN]) -> Self {
+        Self(bytes)
+    }
+
+    /// Parse a key in the hexadecimal form shown by the Victron Connect app.
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        let hex = hex.trim().as_bytes();
+        if hex.len() != ENCRYPTION_KEY_LEN * 2 {
+            return Err(Error::InvalidDeviceEncryptionKey);
+        }
+
+        let mut bytes = [0; ENCRYPTION_KEY_LEN];
+        for (byte, digits) in bytes.iter_mut().zip(hex.chunks_exact(2)) {
+            *byte = (hex_digit(digits[0])? << 4) | hex_digit(digits[1])?;
+        }
+
+        Ok(Self(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; ENCRYPTION_KEY_LEN] {
+        &self.0
+    }
+
+    /// The first byte of the key, which is published unencrypted in every advertisement.
+    pub(crate) fn key_check(&self) -> u8 {
+        self.0[0]
+    }
+}
+
+fn hex_digit(digit: u8) -> Result<u8> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(Error::InvalidDeviceEncryptionKey),
+    }
+}
+
+impl From<[u8; ENCRYPTION_KEY_LEN]> for EncryptionKey {
+    fn from(bytes: [u8; ENCRYPTION_KEY_LEN]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for EncryptionKey {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        let bytes = bytes
+            .try_into()
+            .map_err(|_| Error::InvalidDeviceEncryptionKey)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl FromStr for EncryptionKey {
+    type Err = Error;
+
+    fn from_str(hex: &str) -> Result<Self> {
+        Self::from_hex(hex)
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EncryptionKey(<redacted>)")
+    }
+}
+
+impl Drop for EncryptionKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for EncryptionKey {}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use std::format;
+
+    #[test]
+    fn test_encryption_key_from_hex() {
+        let key = EncryptionKey::from_hex("0df4d0395b7d1a876c0c33ecb9e70dcd").unwrap();
+
+        assert_eq!(
+            key.as_bytes(),
+            &[
+                0x0D, 0xF4, 0xD0, 0x39, 0x5B, 0x7D, 0x1A, 0x87, 0x6C, 0x0C, 0x33, 0xEC, 0xB9, 0xE7,
+                0x0D, 0xCD
+            ]
+        );
+        assert_eq!(
+            " 0DF4D0395B7D1A876C0C33ECB9E70DCD\n"
+                .parse::<EncryptionKey>()
+                .unwrap(),
+            key
+        );
+    }
+
+    #[test]
+    fn test_encryption_key_invalid() {
+        assert!(matches!(
+            EncryptionKey::from_hex(""),
+            Err(Error::InvalidDeviceEncryptionKey)
+        ));
+        assert!(matches!(
+            EncryptionKey::from_hex("0df4d0395b7d1a876c0c33ecb9e70d"),
+            Err(Error::InvalidDeviceEncryptionKey)
+        ));
+        assert!(matches!(
+            EncryptionKey::from_hex("0df4d0395b7d1a876c0c33ecb9e70dcg"),
+            Err(Error::InvalidDeviceEncryptionKey)
+        ));
+        assert!(matches!(
+            EncryptionKey::try_from([0u8; 15].as_slice()),
+            Err(Error::InvalidDeviceEncryptionKey)
+        ));
+    }
+
+    #[test]
+    fn test_encryption_key_debug_is_redacted() {
+        let key = EncryptionKey::new([0xAB; ENCRYPTION_KEY_LEN]);
+
+        let debug = format!("{key:?}");
+
+        assert!(!debug.contains("ab") && !debug.contains("AB") && !debug.contains("171"));
+    }
+}
@@ -1,5 +1,4 @@
 use aes::cipher::StreamCipherError;
-use num_enum::TryFromPrimitiveError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -27,19 +26,11 @@ pub enum Error {
     #[error("Incorrect device encryption key. The Device encryption key provided is not correct for this device.")]
     IncorrectDeviceEncryptionKey,
     #[error(
-        "Invalid device encryption key. The Device encryption key provided is of the wrong length."
+        "Invalid device encryption key. The Device encryption key should be 16 bytes, written as 32 hexadecimal digits."
     )]
     InvalidDeviceEncryptionKey,
-    #[error("Unsupported device type. Please raise an issue at https://github.com/felixwatts/victron_ble quoting the device type code: {0}")]
-    UnsupportedDeviceType(u8),
     #[error("Channel closed by client")]
     ClientClosedChannel,
-    #[error("Invalid mode: {0}")]
-    InvalidMode(TryFromPrimitiveError<crate::model::Mode>),
-    #[error("Invalid error state: {0}")]
-    InvalidErrorState(TryFromPrimitiveError<crate::model::ErrorState>),
-    #[error("Invalid alarm reason")]
-    InvalidAlarmReason,
     #[error("Invalid aux input type: {0}")]
     InvalidAuxInputType(u64),
     #[error("The data was shorter than expected.")]
@@ -48,6 +39,57 @@ pub enum Error {
     InvalidAcInState,
     #[error("Invalid alarm notification")]
     InvalidAlarmNotification,
+    #[error(
+        "A value is out of the range that can be represented in the Manufacturer Data record."
+    )]
+    ValueOutOfRange,
+    #[error("The data was longer than can fit in the Manufacturer Data record.")]
+    DataTooLong,
+    #[error("No key in the key ring matches the device with product ID {product_id:#06x} and key check byte {key_check:#04x}.")]
+    NoMatchingKey { product_id: u16, key_check: u8 },
+    #[error("More than one key in the key ring matches the device with product ID {product_id:#06x} and key check byte {key_check:#04x}. Store the key against the device address.")]
+    AmbiguousKey { product_id: u16, key_check: u8 },
+    #[error("The key ring is full.")]
+    KeyRingFull,
+    #[error("The advertisement nonce {nonce:#06x} is behind the last nonce {last_nonce:#06x}. The advertisement may have been replayed.")]
+    PossibleReplay { nonce: u16, last_nonce: u16 },
+}
+
+impl Error {
+    /// Whether the error leaves the stream usable. Transient errors affect a single
+    /// advertisement or the Bluetooth adapter, which can be acquired again. Other errors,
+    /// such as an invalid key or the client closing the channel, are fatal.
+    ///
+    /// `IncorrectDeviceEncryptionKey` is fatal however the device was selected, since a
+    /// misconfigured key fails for every advertisement and retrying cannot fix it. A device
+    /// selected by name may share the name with another device, so select devices by address
+    /// where several devices share a name.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            #[cfg(feature = "bluetooth")]
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            Error::Bluetooth(_) => true,
+            Error::BluetoothAdapterNotFound
+            | Error::BluetoothDeviceNotFound
+            | Error::BluetoothEventStreamClosed
+            | Error::RecordTooBig
+            | Error::WrongAdvertisement
+            | Error::DecryptionFailed(_)
+            | Error::InvalidAuxInputType(_)
+            | Error::DataTooShort
+            | Error::InvalidAcInState
+            | Error::InvalidAlarmNotification
+            | Error::NoMatchingKey { .. }
+            | Error::PossibleReplay { .. } => true,
+            Error::IncorrectDeviceEncryptionKey
+            | Error::InvalidDeviceEncryptionKey
+            | Error::ClientClosedChannel
+            | Error::ValueOutOfRange
+            | Error::DataTooLong
+            | Error::AmbiguousKey { .. }
+            | Error::KeyRingFull => false,
+        }
+    }
 }
 
 #[cfg(target_os = "macos")]
@@ -72,16 +114,4 @@ impl From<StreamCipherError> for Error {
     }
 }
 
-impl From<TryFromPrimitiveError<crate::model::Mode>> for Error {
-    fn from(e: TryFromPrimitiveError<crate::model::Mode>) -> Self {
-        Error::InvalidMode(e)
-    }
-}
-
-impl From<TryFromPrimitiveError<crate::model::ErrorState>> for Error {
-    fn from(e: TryFromPrimitiveError<crate::model::ErrorState>) -> Self {
-        Error::InvalidErrorState(e)
-    }
-}
-
 pub type Result<T> = core::result::Result<T, Error>;
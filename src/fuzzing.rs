@@ -0,0 +1,89 @@
+//! Entry points used by the fuzz targets in the `fuzz` directory. They expose
+//! crate internals which are not part of the public API.
+
+use crate::bit_reader::BitReader;
+use crate::err::*;
+use crate::model::*;
+
+/// Read fields from the second half of the data, using the first half as the
+/// field widths. The low 6 bits of each width byte give the number of bits
+/// minus one and the high bit selects a signed field.
+fn read_bits(data: &[u8]) -> Result<()> {
+    let (widths, payload) = data.split_at(data.len() / 2);
+    let mut reader = BitReader::new(payload);
+
+    for width in widths {
+        let num_bits = (*width & 0x3F) as usize + 1;
+        if *width & 0x80 == 0 {
+            reader.read_unsigned_field(num_bits, u64::MAX, 0.01, -40.0)?;
+        } else {
+            reader.read_signed_field(num_bits, i64::MAX, 0.01)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The key used to decrypt the input of the `parse_manufacturer_data` fuzz target.
+pub const FUZZ_ENCRYPTION_KEY: [u8; 16] = [
+    0x0D, 0xF4, 0xD0, 0x39, 0x5B, 0x7D, 0x1A, 0x87, 0x6C, 0x0C, 0x33, 0xEC, 0xB9, 0xE7, 0x0D, 0xCD,
+];
+
+/// Run one input through the code exercised by the named fuzz target.
+/// Errors are expected and ignored; a panic is a bug.
+pub fn run_target(target: &str, data: &[u8]) {
+    let _ = match target {
+        "parse_manufacturer_data" => {
+            let key = crate::EncryptionKey::new(FUZZ_ENCRYPTION_KEY);
+            crate::parse_manufacturer_data(data, &key).map(|_| ())
+        }
+        "bit_reader" => read_bits(data),
+        "test_record_state" => TestRecordState::parse(data).map(|_| ()),
+        "solar_charger_state" => SolarChargerState::parse(data).map(|_| ()),
+        "battery_monitor_state" => BatteryMonitorState::parse(data).map(|_| ()),
+        "inverter_state" => InverterState::parse(data).map(|_| ()),
+        "dc_dc_converter_state" => DcDcConverterState::parse(data).map(|_| ()),
+        "smart_lithium_state" => SmartLithiumState::parse(data).map(|_| ()),
+        "inverter_rs_state" => InverterRsState::parse(data).map(|_| ()),
+        "gx_device_state" => GxDeviceState::parse(data).map(|_| ()),
+        "ac_charger_state" => AcChargerState::parse(data).map(|_| ()),
+        "smart_battery_protect_state" => SmartBatteryProtectState::parse(data).map(|_| ()),
+        "lynx_smart_bms_state" => LynxSmartBmsState::parse(data).map(|_| ()),
+        "multi_rs_state" => MultiRsState::parse(data).map(|_| ()),
+        "ve_bus_state" => VeBusState::parse(data).map(|_| ()),
+        "dc_energy_meter_state" => DcEnergyMeterState::parse(data).map(|_| ()),
+        "orion_xs_state" => OrionXsState::parse(data).map(|_| ()),
+        _ => panic!("Unknown fuzz target: {target}"),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use std::{fs, path::Path};
+
+    /// Replay the regression corpus checked in under `fuzz/corpus`.
+    #[test]
+    fn test_fuzz_corpus() {
+        let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/corpus");
+        if !corpus.is_dir() {
+            // `fuzz` is excluded from the published package, so there is nothing to replay.
+            return;
+        }
+        let mut count = 0;
+
+        for target in fs::read_dir(corpus).unwrap() {
+            let target = target.unwrap();
+            let target_name = target.file_name().into_string().unwrap();
+            for input in fs::read_dir(target.path()).unwrap() {
+                let data = fs::read(input.unwrap().path()).unwrap();
+                run_target(&target_name, &data);
+                count += 1;
+            }
+        }
+
+        assert!(count > 0);
+    }
+}
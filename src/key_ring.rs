@@ -0,0 +1,382 @@
+use crate::decoder::Decoder;
+use crate::encryption_key::EncryptionKey;
+use crate::err::*;
+use crate::model::{AdvertisementHeader, DeviceState};
+use thiserror::Error;
+
+/// The default number of keys a `KeyRing` can hold.
+pub const DEFAULT_KEY_RING_CAPACITY: usize = 32;
+
+/// An error decoding an advertisement with a `KeyRing`, together with the address of the
+/// device that published it.
+#[derive(Error, Debug)]
+#[error("Could not decode the advertisement of device {address:?}: {error}")]
+pub struct KeyRingError<A> {
+    pub address: A,
+    #[source]
+    pub error: Error,
+}
+
+impl<A> From<KeyRingError<A>> for Error {
+    fn from(e: KeyRingError<A>) -> Self {
+        e.error
+    }
+}
+
+/// A set of device encryption keys, used to decode the advertisements of many
+/// devices without knowing in advance which key belongs to which device.
+///
+/// Keys can be stored against a device address, in which case they are only used
+/// for that device, or without an address, in which case they are tried for any
+/// device whose advertisement carries a matching key check byte. The address type
+/// is chosen by the caller, typically the Bluetooth address of the platform.
+///
+/// The key ring has a fixed capacity of `N` keys so that it can be used without
+/// an allocator. A `Decoder` is kept for each key so that the cipher is only set up
+/// once.
+#[derive(Debug, Clone)]
+pub struct KeyRing<A, const N: usize = DEFAULT_KEY_RING_CAPACITY> {
+    entries: [Option<KeyRingEntry<A>>; N],
+}
+
+#[derive(Debug, Clone)]
+struct KeyRingEntry<A> {
+    address: Option<A>,
+    decoder: Decoder,
+}
+
+impl<A: PartialEq, const N: usize> KeyRing<A, N> {
+    pub fn new() -> Self {
+        Self {
+            entries: core::array::from_fn(|_| None),
+        }
+    }
+
+    /// Store the key for the device with the given address, replacing any key
+    /// already stored for it.
+    pub fn insert(&mut self, address: A, key: EncryptionKey) -> Result<()> {
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .flatten()
+            .find(|entry| entry.address.as_ref() == Some(&address))
+        {
+            entry.decoder = Decoder::from(key);
+            return Ok(());
+        }
+
+        self.push(KeyRingEntry {
+            address: Some(address),
+            decoder: Decoder::from(key),
+        })
+    }
+
+    /// Store a key that is not tied to a device address. It is tried for any device
+    /// that has no key of its own and whose key check byte matches.
+    pub fn insert_without_address(&mut self, key: EncryptionKey) -> Result<()> {
+        if self
+            .entries
+            .iter()
+            .flatten()
+            .any(|entry| entry.address.is_none() && *entry.decoder.key() == key)
+        {
+            return Ok(());
+        }
+
+        self.push(KeyRingEntry {
+            address: None,
+            decoder: Decoder::from(key),
+        })
+    }
+
+    /// Remove the key stored for the device with the given address. Returns whether a
+    /// key was removed.
+    pub fn remove(&mut self, address: &A) -> bool {
+        for slot in self.entries.iter_mut() {
+            if slot
+                .as_ref()
+                .is_some_and(|entry| entry.address.as_ref() == Some(address))
+            {
+                *slot = None;
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Decrypt and parse the manufacturer data published by the device with the given
+    /// address, picking the key to use.
+    ///
+    /// The key stored for the address is used if there is one. Otherwise each key stored
+    /// without an address whose first byte matches the key check byte of the advertisement
+    /// is tried. The payload carries no checksum, so a wrong key that shares the first byte
+    /// decrypts it to noise rather than failing. A key is taken to be right if the payload
+    /// parses to a state without unknown values, see `DeviceState::has_unknown_values`.
+    ///
+    /// If exactly one key is right its state is returned. If only one key matches the key
+    /// check byte its state is returned even if it has unknown values. If there is no
+    /// matching key `Error::NoMatchingKey` is returned, and if no single key can be picked
+    /// `Error::AmbiguousKey` is returned. Record types with no enumerated fields, such as
+    /// the GX device and Lynx Smart BMS, can never be told apart, so store keys against the
+    /// device address where it is known.
+    ///
+    /// Errors are returned together with the address so that the device can be identified.
+    pub fn decode(
+        &self,
+        manufacturer_data: &[u8],
+        address: &A,
+    ) -> core::result::Result<DeviceState, KeyRingError<A>>
+    where
+        A: Clone,
+    {
+        self.decode_inner(manufacturer_data, address)
+            .map_err(|error| KeyRingError {
+                address: address.clone(),
+                error,
+            })
+    }
+
+    fn decode_inner(&self, manufacturer_data: &[u8], address: &A) -> Result<DeviceState> {
+        let header = AdvertisementHeader::parse(manufacturer_data)?;
+
+        if let Some(decoder) = self.decoder_for_address(address) {
+            return decoder.decode(manufacturer_data);
+        }
+
+        let mut plausible = None;
+        let mut implausible = None;
+        let mut implausible_count = 0;
+        for decoder in self.candidate_decoders(&header) {
+            match decoder.decode(manufacturer_data) {
+                Ok(device_state) if !device_state.has_unknown_values() => {
+                    if plausible.is_some() {
+                        return Err(Error::AmbiguousKey {
+                            product_id: header.product_id,
+                            key_check: header.key_check,
+                        });
+                    }
+                    plausible = Some(device_state);
+                }
+                result => {
+                    implausible_count += 1;
+                    implausible.get_or_insert(result);
+                }
+            }
+        }
+
+        match (plausible, implausible) {
+            (Some(device_state), _) => Ok(device_state),
+            (None, Some(result)) if implausible_count == 1 => result,
+            (None, Some(_)) => Err(Error::AmbiguousKey {
+                product_id: header.product_id,
+                key_check: header.key_check,
+            }),
+            (None, None) => Err(Error::NoMatchingKey {
+                product_id: header.product_id,
+                key_check: header.key_check,
+            }),
+        }
+    }
+
+    fn decoder_for_address(&self, address: &A) -> Option<&Decoder> {
+        self.entries
+            .iter()
+            .flatten()
+            .find(|entry| entry.address.as_ref() == Some(address))
+            .map(|entry| &entry.decoder)
+    }
+
+    fn candidate_decoders<'a>(
+        &'a self,
+        header: &'a AdvertisementHeader,
+    ) -> impl Iterator<Item = &'a Decoder> {
+        self.entries
+            .iter()
+            .flatten()
+            .filter(|entry| entry.address.is_none())
+            .map(|entry| &entry.decoder)
+            .filter(|decoder| header.is_key_candidate(decoder.key()))
+    }
+
+    fn push(&mut self, entry: KeyRingEntry<A>) -> Result<()> {
+        let slot = self
+            .entries
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .ok_or(Error::KeyRingFull)?;
+        *slot = Some(entry);
+        Ok(())
+    }
+}
+
+impl<A: PartialEq, const N: usize> Default for KeyRing<A, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::{ErrorState, GxDeviceState, Mode, SolarChargerState};
+
+    const DEVICE_1: [u8; 6] = [1, 1, 1, 1, 1, 1];
+    const DEVICE_2: [u8; 6] = [2, 2, 2, 2, 2, 2];
+
+    fn key(first: u8, last: u8) -> EncryptionKey {
+        let mut key = [first; 16];
+        key[15] = last;
+        EncryptionKey::new(key)
+    }
+
+    fn device_state() -> DeviceState {
+        DeviceState::SolarCharger(SolarChargerState {
+            mode: Mode::Bulk,
+            error_state: ErrorState::NoError,
+            battery_voltage_v: Some(13.2),
+            battery_current_a: Some(5.0),
+            yield_today_kwh: Some(1.25),
+            pv_power_w: Some(72.0),
+            load_current_a: None,
+        })
+    }
+
+    fn manufacturer_data(key: &EncryptionKey) -> [u8; 24] {
+        crate::encode_manufacturer_data(&device_state(), 0xA055, 0x2221, key).unwrap()
+    }
+
+    #[test]
+    fn test_key_ring_decode_by_address() {
+        let mut key_ring = KeyRing::<[u8; 6], 4>::new();
+        key_ring.insert(DEVICE_1, key(0xAA, 1)).unwrap();
+        key_ring.insert(DEVICE_2, key(0xAA, 2)).unwrap();
+
+        assert!(key_ring
+            .decode(&manufacturer_data(&key(0xAA, 1)), &DEVICE_1)
+            .is_ok());
+        assert!(key_ring
+            .decode(&manufacturer_data(&key(0xAA, 2)), &DEVICE_2)
+            .is_ok());
+        assert!(matches!(
+            key_ring.decode(&manufacturer_data(&key(0xBB, 2)), &DEVICE_1),
+            Err(KeyRingError {
+                address: DEVICE_1,
+                error: Error::IncorrectDeviceEncryptionKey
+            })
+        ));
+    }
+
+    #[test]
+    fn test_key_ring_decode_by_key_check() {
+        let mut key_ring = KeyRing::<[u8; 6], 4>::new();
+        key_ring.insert_without_address(key(0xAA, 1)).unwrap();
+        key_ring.insert_without_address(key(0xBB, 2)).unwrap();
+        key_ring.insert_without_address(key(0xCC, 3)).unwrap();
+
+        let result = key_ring.decode(&manufacturer_data(&key(0xBB, 2)), &DEVICE_1);
+
+        assert_eq!(result.unwrap(), device_state());
+    }
+
+    #[test]
+    fn test_key_ring_decode_key_check_collision() {
+        let mut key_ring = KeyRing::<[u8; 6], 4>::new();
+        key_ring.insert_without_address(key(0xAA, 1)).unwrap();
+        key_ring.insert_without_address(key(0xAA, 2)).unwrap();
+        let manufacturer_data = manufacturer_data(&key(0xAA, 2));
+
+        // Both keys pass the key check, but the wrong one decrypts the payload to a state
+        // with unknown values.
+        assert!(Decoder::from(key(0xAA, 1))
+            .decode(&manufacturer_data)
+            .unwrap()
+            .has_unknown_values());
+        let result = key_ring.decode(&manufacturer_data, &DEVICE_1);
+
+        assert_eq!(result.unwrap(), device_state());
+    }
+
+    #[test]
+    fn test_key_ring_decode_ambiguous_key() {
+        let mut key_ring = KeyRing::<[u8; 6], 4>::new();
+        key_ring.insert_without_address(key(0xAA, 1)).unwrap();
+        key_ring.insert_without_address(key(0xAA, 2)).unwrap();
+        key_ring.insert(DEVICE_2, key(0xAA, 2)).unwrap();
+        // A GX device state has no enumerated fields, so any key yields a plausible state.
+        let device_state = DeviceState::GxDevice(GxDeviceState {
+            battery_voltage_v: Some(13.2),
+            pv_power_w: Some(350.0),
+            soc_percent: Some(87.0),
+            battery_power_w: Some(-120.0),
+            dc_power_w: Some(40.0),
+        });
+        let manufacturer_data =
+            crate::encode_manufacturer_data(&device_state, 0xA3C5, 0x2221, &key(0xAA, 2)).unwrap();
+
+        let result = key_ring.decode(&manufacturer_data, &DEVICE_1);
+
+        assert!(matches!(
+            result,
+            Err(KeyRingError {
+                address: DEVICE_1,
+                error: Error::AmbiguousKey {
+                    product_id: 0xA3C5,
+                    key_check: 0xAA
+                }
+            })
+        ));
+        assert_eq!(
+            key_ring.decode(&manufacturer_data, &DEVICE_2).unwrap(),
+            device_state
+        );
+    }
+
+    #[test]
+    fn test_key_ring_decode_no_matching_key() {
+        let mut key_ring = KeyRing::<[u8; 6], 4>::new();
+        key_ring.insert(DEVICE_2, key(0xAA, 1)).unwrap();
+        key_ring.insert_without_address(key(0xBB, 1)).unwrap();
+
+        let result = key_ring.decode(&manufacturer_data(&key(0xAA, 1)), &DEVICE_1);
+
+        assert!(matches!(
+            result,
+            Err(KeyRingError {
+                address: DEVICE_1,
+                error: Error::NoMatchingKey {
+                    product_id: 0xA055,
+                    key_check: 0xAA
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn test_key_ring_insert_and_remove() {
+        let mut key_ring = KeyRing::<[u8; 6], 2>::new();
+        assert!(key_ring.is_empty());
+
+        key_ring.insert(DEVICE_1, key(0xAA, 1)).unwrap();
+        key_ring.insert(DEVICE_1, key(0xAA, 2)).unwrap();
+        key_ring.insert_without_address(key(0xBB, 1)).unwrap();
+        key_ring.insert_without_address(key(0xBB, 1)).unwrap();
+        assert_eq!(key_ring.len(), 2);
+        assert!(matches!(
+            key_ring.insert(DEVICE_2, key(0xCC, 1)),
+            Err(Error::KeyRingFull)
+        ));
+
+        assert!(key_ring.remove(&DEVICE_1));
+        assert!(!key_ring.remove(&DEVICE_1));
+        key_ring.insert(DEVICE_2, key(0xCC, 1)).unwrap();
+        assert_eq!(key_ring.len(), 2);
+    }
+}
@@ -2,22 +2,67 @@
 #![doc = include_str!("../README.md")]
 
 mod bit_reader;
+mod bit_writer;
 mod bluetooth;
+mod decoder;
+mod encryption_key;
 mod err;
+#[cfg(any(test, feature = "fuzzing"))]
+#[doc(hidden)]
+pub mod fuzzing;
+mod key_ring;
 mod model;
+mod nonce_tracker;
 mod record;
 
+pub use crate::decoder::*;
+pub use crate::encryption_key::*;
 pub use crate::err::*;
+pub use crate::key_ring::*;
 #[cfg(feature = "bluetooth")]
-pub use bluetooth::open_stream;
+pub use bluetooth::{
+    discover, discover_with_source, open_multi_stream, open_multi_stream_supervised,
+    open_multi_stream_supervised_with_source, open_multi_stream_with_source, open_stream,
+    open_stream_supervised, open_stream_with_source, Advertisement, AdvertisementSource,
+    AdvertisementStream, Backoff, DeviceDescriptor, DeviceSelector, DiscoveredDevice, Reading,
+    SystemAdvertisementSource,
+};
 pub use model::*;
-use record::Record;
-
+pub use nonce_tracker::*;
 /// Decrypt and parse the content of the manufacturer data published by a Victron device.
+///
+/// This sets up the cipher for the key on every call. Use a `Decoder` to decode many
+/// advertisements with the same key.
 pub fn parse_manufacturer_data(
     manufacturer_data: &[u8],
-    device_encryption_key: &[u8],
+    device_encryption_key: &EncryptionKey,
 ) -> Result<DeviceState> {
-    let record = Record::new(manufacturer_data, device_encryption_key)?;
-    DeviceState::parse(&record)
+    Decoder::new(device_encryption_key).decode(manufacturer_data)
+}
+
+/// Decrypt and parse the content of the manufacturer data published by a Victron device,
+/// also returning the unencrypted header which identifies the product that sent it.
+///
+/// Use `AdvertisementHeader::product` on the returned header to look the product up in the
+/// catalogue of known Victron products.
+pub fn parse_manufacturer_data_with_header(
+    manufacturer_data: &[u8],
+    device_encryption_key: &EncryptionKey,
+) -> Result<(AdvertisementHeader, DeviceState)> {
+    Decoder::new(device_encryption_key).decode_with_header(manufacturer_data)
+}
+
+/// Encode and encrypt a device state into the manufacturer data that a Victron device
+/// with the given product ID would publish. This is the inverse of `parse_manufacturer_data`
+/// and is useful for simulating devices and for testing consumers of this crate.
+///
+/// The nonce is used as the IV when encrypting the payload. A real device increments it
+/// whenever the payload changes.
+pub fn encode_manufacturer_data(
+    device_state: &DeviceState,
+    product_id: u16,
+    nonce: u16,
+    device_encryption_key: &EncryptionKey,
+) -> Result<[u8; 24]> {
+    Decoder::new(device_encryption_key).encode(device_state, product_id, nonce)
 }
@@ -1,4 +1,5 @@
 use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
 use crate::err::*;
 
 use super::error_state::ErrorState;
@@ -23,8 +24,8 @@ impl AcChargerState {
     pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
         let mut reader = BitReader::new(payload);
 
-        let mode = Mode::try_from(reader.read_unsigned_int(8)?)?;
-        let error_state = ErrorState::try_from(reader.read_unsigned_int(8)?)?;
+        let mode = Mode::from(reader.read_unsigned_int(8)? as u8);
+        let error_state = ErrorState::from(reader.read_unsigned_int(8)? as u8);
         let battery_voltage1_v = reader.read_unsigned_field(13, 0x1FFF, 0.01, 0.0)?;
         let battery_current1_a = reader.read_unsigned_field(11, 0x7FF, 0.1, 0.0)?;
         let battery_voltage2_v = reader.read_unsigned_field(13, 0x1FFF, 0.01, 0.0)?;
@@ -47,6 +48,28 @@ impl AcChargerState {
             ac_current_a,
         })
     }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(8, u8::from(self.mode) as u64)?;
+        writer.write_unsigned_int(8, u8::from(self.error_state) as u64)?;
+        writer.write_unsigned_field(13, 0x1FFF, 0.01, 0.0, self.battery_voltage1_v)?;
+        writer.write_unsigned_field(11, 0x7FF, 0.1, 0.0, self.battery_current1_a)?;
+        writer.write_unsigned_field(13, 0x1FFF, 0.01, 0.0, self.battery_voltage2_v)?;
+        writer.write_unsigned_field(11, 0x7FF, 0.1, 0.0, self.battery_current2_a)?;
+        writer.write_unsigned_field(13, 0x1FFF, 0.01, 0.0, self.battery_voltage3_v)?;
+        writer.write_unsigned_field(11, 0x7FF, 0.1, 0.0, self.battery_current3_a)?;
+        writer.write_unsigned_field(7, 0x7F, 1.0, -40.0, self.temperature_c)?;
+        writer.write_unsigned_field(9, 0x1FF, 0.1, 0.0, self.ac_current_a)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        matches!(self.mode, Mode::Unknown(_)) || matches!(self.error_state, ErrorState::Unknown(_))
+    }
 }
 
 #[cfg(test)]
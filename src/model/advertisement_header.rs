@@ -0,0 +1,123 @@
+use super::product::Product;
+use crate::encryption_key::EncryptionKey;
+use crate::err::*;
+use crate::record::MANUFACTURER_DATA_RECORD_TYPE;
+
+const MAX_MANUFACTURER_DATA_LEN: usize = 24;
+const HEADER_LEN: usize = 8;
+
+/// The unencrypted header of a Victron extra manufacturer data record.
+///
+/// It can be read without the device encryption key, which makes it useful
+/// for identifying a device before decrypting its state.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct AdvertisementHeader {
+    /// The Victron product ID (model ID) of the device that sent the advertisement.
+    pub product_id: u16,
+    /// The record type, such as solar charger or inverter.
+    pub record_type: u8,
+    /// The nonce (data counter) used as the IV when decrypting the payload.
+    pub nonce: u16,
+    /// The first byte of the device encryption key.
+    pub key_check: u8,
+}
+
+impl AdvertisementHeader {
+    /// Parse the header of the manufacturer data published by a Victron device.
+    pub fn parse(manufacturer_data: &[u8]) -> Result<Self> {
+        match manufacturer_data.first() {
+            None => return Err(Error::DataTooShort),
+            Some(&MANUFACTURER_DATA_RECORD_TYPE) => {}
+            Some(_) => return Err(Error::WrongAdvertisement),
+        }
+
+        if manufacturer_data.len() > MAX_MANUFACTURER_DATA_LEN {
+            return Err(Error::RecordTooBig);
+        }
+
+        if manufacturer_data.len() < HEADER_LEN {
+            return Err(Error::DataTooShort);
+        }
+
+        Ok(Self {
+            product_id: u16::from_le_bytes([manufacturer_data[2], manufacturer_data[3]]),
+            record_type: manufacturer_data[4],
+            nonce: u16::from_le_bytes([manufacturer_data[5], manufacturer_data[6]]),
+            key_check: manufacturer_data[7],
+        })
+    }
+
+    /// The product that sent the advertisement, if it is a known product.
+    pub fn product(&self) -> Option<&'static Product> {
+        Product::from_id(self.product_id)
+    }
+
+    /// Whether the given device encryption key could be the one used to encrypt the payload.
+    pub fn is_key_candidate(&self, device_encryption_key: &EncryptionKey) -> bool {
+        device_encryption_key.key_check() == self.key_check
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_advertisement_header_parse() {
+        let manufacturer_data = hex::decode("1000a0a001e9b3be3ff7262d6e9ff1f8b4").unwrap();
+
+        let result = AdvertisementHeader::parse(&manufacturer_data).unwrap();
+
+        assert_eq!(result.product_id, 0xA0A0);
+        assert!(result.product().is_none());
+        assert_eq!(result.record_type, 0x01);
+        assert_eq!(result.nonce, 0xB3E9);
+        assert_eq!(result.key_check, 0xBE);
+        assert!(result.is_key_candidate(&EncryptionKey::new([0xBE; 16])));
+        assert!(!result.is_key_candidate(&EncryptionKey::new([0x01; 16])));
+    }
+
+    #[test]
+    fn test_advertisement_header_parse_wrong_advertisement() {
+        let manufacturer_data = hex::decode("0200a0a001e9b3be").unwrap();
+
+        let result = AdvertisementHeader::parse(&manufacturer_data);
+
+        assert!(matches!(result, Err(Error::WrongAdvertisement)));
+    }
+
+    #[test]
+    fn test_advertisement_header_parse_too_short() {
+        let manufacturer_data = hex::decode("1000a0a001").unwrap();
+
+        let result = AdvertisementHeader::parse(&manufacturer_data);
+
+        assert!(matches!(result, Err(Error::DataTooShort)));
+    }
+
+    #[test]
+    fn test_advertisement_header_parse_short_wrong_advertisement() {
+        let manufacturer_data = hex::decode("0200").unwrap();
+
+        let result = AdvertisementHeader::parse(&manufacturer_data);
+
+        assert!(matches!(result, Err(Error::WrongAdvertisement)));
+    }
+
+    #[test]
+    fn test_advertisement_header_parse_empty() {
+        let result = AdvertisementHeader::parse(&[]);
+
+        assert!(matches!(result, Err(Error::DataTooShort)));
+    }
+
+    #[test]
+    fn test_advertisement_header_product() {
+        let manufacturer_data = hex::decode("10004aa001e9b3be").unwrap();
+
+        let result = AdvertisementHeader::parse(&manufacturer_data).unwrap();
+
+        assert_eq!(result.product().unwrap().name, "BlueSolar MPPT 100/30 rev2");
+    }
+}
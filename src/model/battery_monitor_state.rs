@@ -1,5 +1,6 @@
 use super::alarm_reason::AlarmReason;
 use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
 use crate::err::*;
 
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
@@ -29,9 +30,46 @@ impl BatteryMonitorState {
 
         let time_to_go_mins = reader.read_unsigned_field(16, 0xFFFF, 1.0, 0.0)?;
         let battery_voltage_v = reader.read_signed_field(16, 0x7FFF, 0.01)?;
-        let alarm_reason =
-            AlarmReason::from_bits(reader.read_signed_int(16)?).ok_or(Error::InvalidAlarmReason)?;
+        let alarm_reason = AlarmReason::from_bits_retain(reader.read_signed_int(16)?);
 
+        let aux_input = AuxInput::parse(&mut reader)?;
+        let battery_current_a = reader.read_signed_field(22, 0x3FFFFF, 0.001)?;
+        let consumed_amp_hours_ah = reader.read_unsigned_field(20, 0xFFFFF, -0.1, 0.0)?;
+        let state_of_charge_pct = reader.read_unsigned_field(10, 0x3FF, 0.1, 0.0)?;
+
+        Ok(Self {
+            time_to_go_mins,
+            battery_voltage_v,
+            alarm_reason,
+            aux_input,
+            battery_current_a,
+            consumed_amp_hours_ah,
+            state_of_charge_pct,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_field(16, 0xFFFF, 1.0, 0.0, self.time_to_go_mins)?;
+        writer.write_signed_field(16, 0x7FFF, 0.01, self.battery_voltage_v)?;
+        writer.write_signed_int(16, self.alarm_reason.bits())?;
+        self.aux_input.encode(&mut writer)?;
+        writer.write_signed_field(22, 0x3FFFFF, 0.001, self.battery_current_a)?;
+        writer.write_unsigned_field(20, 0xFFFFF, -0.1, 0.0, self.consumed_amp_hours_ah)?;
+        writer.write_unsigned_field(10, 0x3FF, 0.1, 0.0, self.state_of_charge_pct)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        AlarmReason::from_bits(self.alarm_reason.bits()).is_none()
+    }
+}
+
+impl AuxInput {
+    pub(crate) fn parse(reader: &mut BitReader) -> Result<Self> {
         // we need to read the next two fields out of order because
         // the format of the former depends on the latter.
         let mut aux_input_reader = reader.clone();
@@ -54,18 +92,27 @@ impl BatteryMonitorState {
             t => return Err(Error::InvalidAuxInputType(t)),
         };
 
-        let battery_current_a = reader.read_signed_field(22, 0x3FFFFF, 0.001)?;
-        let consumed_amp_hours_ah = reader.read_unsigned_field(20, 0xFFFFF, -0.1, 0.0)?;
-        let state_of_charge_pct = reader.read_unsigned_field(10, 0x3FF, 0.1, 0.0)?;
+        Ok(aux_input)
+    }
 
-        Ok(Self {
-            time_to_go_mins,
-            battery_voltage_v,
-            alarm_reason,
-            aux_input,
-            battery_current_a,
-            consumed_amp_hours_ah,
-            state_of_charge_pct,
-        })
+    pub(crate) fn encode(&self, writer: &mut BitWriter) -> Result<()> {
+        match self {
+            AuxInput::VoltageV(v) => {
+                writer.write_signed_field(16, 0x7FFF, 0.01, Some(*v))?;
+                writer.write_unsigned_int(2, 0)
+            }
+            AuxInput::MidVoltageV(v) => {
+                writer.write_unsigned_field(16, 0xFFFF, 0.01, 0.0, Some(*v))?;
+                writer.write_unsigned_int(2, 1)
+            }
+            AuxInput::TemperatureK(t) => {
+                writer.write_unsigned_field(16, 0xFFFF, 0.01, 0.0, Some(*t))?;
+                writer.write_unsigned_int(2, 2)
+            }
+            AuxInput::None => {
+                writer.write_unsigned_int(16, 0xFFFF)?;
+                writer.write_unsigned_int(2, 3)
+            }
+        }
     }
 }
@@ -0,0 +1,121 @@
+use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
+use crate::err::*;
+
+use super::error_state::ErrorState;
+use super::mode::Mode;
+use super::off_reason::OffReason;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct DcDcConverterState {
+    pub mode: Mode,
+    pub error_state: ErrorState,
+    pub input_voltage_v: Option<f32>,
+    pub output_voltage_v: Option<f32>,
+    pub off_reason: OffReason,
+}
+
+impl DcDcConverterState {
+    pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(payload);
+
+        let mode = Mode::from(reader.read_unsigned_int(8)? as u8);
+        let error_state = ErrorState::from(reader.read_unsigned_int(8)? as u8);
+        let input_voltage_v = reader.read_unsigned_field(16, 0xFFFF, 0.01, 0.0)?;
+        let output_voltage_v = reader.read_signed_field(16, 0x7FFF, 0.01)?;
+        let off_reason = OffReason::from_bits_retain(reader.read_unsigned_int(32)? as u32);
+
+        Ok(Self {
+            mode,
+            error_state,
+            input_voltage_v,
+            output_voltage_v,
+            off_reason,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(8, u8::from(self.mode) as u64)?;
+        writer.write_unsigned_int(8, u8::from(self.error_state) as u64)?;
+        writer.write_unsigned_field(16, 0xFFFF, 0.01, 0.0, self.input_voltage_v)?;
+        writer.write_signed_field(16, 0x7FFF, 0.01, self.output_voltage_v)?;
+        writer.write_unsigned_int(32, self.off_reason.bits() as u64)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        matches!(self.mode, Mode::Unknown(_))
+            || matches!(self.error_state, ErrorState::Unknown(_))
+            || OffReason::from_bits(self.off_reason.bits()).is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Raw: [0x04, 0x00, 0x6B, 0x05, 0x8C, 0x05, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    // Orion Smart in absorption, fed from a running alternator
+    #[test]
+    fn test_dc_dc_converter_state_parse_1() {
+        let test_data = [
+            0x04, 0x00, 0x6B, 0x05, 0x8C, 0x05, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = DcDcConverterState::parse(&test_data).unwrap();
+
+        assert_eq!(result.mode, Mode::Absorption);
+        assert_eq!(result.error_state, ErrorState::NoError);
+        assert!((result.input_voltage_v.unwrap() - 13.87).abs() < f32::EPSILON);
+        assert!((result.output_voltage_v.unwrap() - 14.20).abs() < f32::EPSILON);
+        assert!(result.off_reason.is_empty());
+    }
+
+    // Raw: [0x00, 0x00, 0xE2, 0x04, 0xFF, 0x7F, 0x81, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    // Orion Smart switched off by engine shutdown detection
+    #[test]
+    fn test_dc_dc_converter_state_parse_2() {
+        let test_data = [
+            0x00, 0x00, 0xE2, 0x04, 0xFF, 0x7F, 0x81, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = DcDcConverterState::parse(&test_data).unwrap();
+
+        assert_eq!(result.mode, Mode::Off);
+        assert_eq!(result.error_state, ErrorState::NoError);
+        assert!((result.input_voltage_v.unwrap() - 12.50).abs() < f32::EPSILON);
+        assert!(result.output_voltage_v.is_none());
+        assert_eq!(
+            result.off_reason,
+            OffReason::NoInputPower | OffReason::EngineShutdown
+        );
+    }
+
+    // Raw: [0x08, 0x99, 0x6B, 0x05, 0x8C, 0x05, 0x01, 0x00, 0x00, 0x80, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    // Orion Smart reporting a mode, error and off reason from newer firmware
+    #[test]
+    fn test_dc_dc_converter_state_parse_unknown_values() {
+        let test_data = [
+            0x08, 0x99, 0x6B, 0x05, 0x8C, 0x05, 0x01, 0x00, 0x00, 0x80, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = DcDcConverterState::parse(&test_data).unwrap();
+
+        assert_eq!(result.mode, Mode::Unknown(0x08));
+        assert_eq!(result.error_state, ErrorState::Unknown(0x99));
+        assert!((result.input_voltage_v.unwrap() - 13.87).abs() < f32::EPSILON);
+        assert!((result.output_voltage_v.unwrap() - 14.20).abs() < f32::EPSILON);
+        assert!(result.off_reason.contains(OffReason::NoInputPower));
+        assert_eq!(result.off_reason.bits(), 0x8000_0001);
+        assert!(result.has_unknown_values());
+        assert_eq!(result.encode().unwrap(), test_data);
+    }
+}
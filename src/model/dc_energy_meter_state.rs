@@ -0,0 +1,166 @@
+use super::alarm_reason::AlarmReason;
+use super::battery_monitor_state::AuxInput;
+use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
+use crate::err::*;
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DcEnergyMeterState {
+    pub meter_type: MeterType,
+    pub battery_voltage_v: Option<f32>,
+    pub alarm_reason: AlarmReason,
+    pub aux_input: AuxInput,
+    pub battery_current_a: Option<f32>,
+}
+
+/// What the DC energy meter has been configured to measure. Negative values
+/// are sources, positive values are loads.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, FromPrimitive, IntoPrimitive,
+)]
+#[repr(i16)]
+pub enum MeterType {
+    SolarCharger = -9,
+    WindCharger = -8,
+    ShaftGenerator = -7,
+    Alternator = -6,
+    FuelCell = -5,
+    WaterGenerator = -4,
+    DcDcCharger = -3,
+    AcCharger = -2,
+    GenericSource = -1,
+    BatteryMonitor = 0,
+    GenericLoad = 1,
+    ElectricDrive = 2,
+    Fridge = 3,
+    WaterPump = 4,
+    BilgePump = 5,
+    DcSystem = 6,
+    Inverter = 7,
+    WaterHeater = 8,
+    /// A meter type that is not known to this version of the crate, holding the raw value.
+    #[num_enum(catch_all)]
+    Unknown(i16),
+}
+
+impl DcEnergyMeterState {
+    pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(payload);
+
+        let meter_type = MeterType::from(reader.read_signed_int(16)? as i16);
+        let battery_voltage_v = reader.read_signed_field(16, 0x7FFF, 0.01)?;
+        let alarm_reason = AlarmReason::from_bits_retain(reader.read_signed_int(16)?);
+        let aux_input = AuxInput::parse(&mut reader)?;
+        let battery_current_a = reader.read_signed_field(22, 0x3FFFFF, 0.001)?;
+
+        Ok(Self {
+            meter_type,
+            battery_voltage_v,
+            alarm_reason,
+            aux_input,
+            battery_current_a,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_signed_int(16, i16::from(self.meter_type) as i64)?;
+        writer.write_signed_field(16, 0x7FFF, 0.01, self.battery_voltage_v)?;
+        writer.write_signed_int(16, self.alarm_reason.bits())?;
+        self.aux_input.encode(&mut writer)?;
+        writer.write_signed_field(22, 0x3FFFFF, 0.001, self.battery_current_a)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        matches!(self.meter_type, MeterType::Unknown(_))
+            || AlarmReason::from_bits(self.alarm_reason.bits()).is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Raw: [0xFA, 0xFF, 0x48, 0x05, 0x00, 0x00, 0x77, 0x74, 0xBA, 0x95, 0x02, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    // SmartShunt measuring an alternator, with a temperature sensor on the aux input
+    #[test]
+    fn test_dc_energy_meter_state_parse_1() {
+        let test_data = [
+            0xFA, 0xFF, 0x48, 0x05, 0x00, 0x00, 0x77, 0x74, 0xBA, 0x95, 0x02, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = DcEnergyMeterState::parse(&test_data).unwrap();
+
+        assert_eq!(result.meter_type, MeterType::Alternator);
+        assert!((result.battery_voltage_v.unwrap() - 13.52).abs() < 0.001);
+        assert!(result.alarm_reason.is_empty());
+        match result.aux_input {
+            AuxInput::TemperatureK(t) => assert!((t - 298.15).abs() < 0.001),
+            other => panic!("Expected a temperature, got {other:?}"),
+        }
+        assert!((result.battery_current_a.unwrap() - 42.35).abs() < 0.001);
+    }
+
+    // Raw: [0x03, 0x00, 0xE8, 0x04, 0x00, 0x00, 0x00, 0x00, 0x43, 0xCF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    // SmartShunt measuring a fridge, aux input unused
+    #[test]
+    fn test_dc_energy_meter_state_parse_2() {
+        let test_data = [
+            0x03, 0x00, 0xE8, 0x04, 0x00, 0x00, 0x00, 0x00, 0x43, 0xCF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = DcEnergyMeterState::parse(&test_data).unwrap();
+
+        assert_eq!(result.meter_type, MeterType::Fridge);
+        assert!((result.battery_voltage_v.unwrap() - 12.56).abs() < 0.001);
+        assert!(result.alarm_reason.is_empty());
+        assert_eq!(result.aux_input, AuxInput::None);
+        assert!((result.battery_current_a.unwrap() + 3.12).abs() < 0.001);
+    }
+
+    // Raw: [0x03, 0x00, 0xE8, 0x04, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    // SmartShunt measuring a fridge, current not available
+    #[test]
+    fn test_dc_energy_meter_state_parse_current_not_available() {
+        let test_data = [
+            0x03, 0x00, 0xE8, 0x04, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = DcEnergyMeterState::parse(&test_data).unwrap();
+
+        assert_eq!(result.meter_type, MeterType::Fridge);
+        assert!((result.battery_voltage_v.unwrap() - 12.56).abs() < 0.001);
+        assert_eq!(result.aux_input, AuxInput::None);
+        assert_eq!(result.battery_current_a, None);
+    }
+
+    // Raw: [0x2A, 0x00, 0xE2, 0x04, 0x00, 0x40, 0xFF, 0xFF, 0xF3, 0xD8, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    // SmartShunt with a meter type and alarm bit from newer firmware
+    #[test]
+    fn test_dc_energy_meter_state_parse_unknown_values() {
+        let test_data = [
+            0x2A, 0x00, 0xE2, 0x04, 0x00, 0x40, 0xFF, 0xFF, 0xF3, 0xD8, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = DcEnergyMeterState::parse(&test_data).unwrap();
+
+        assert_eq!(result.meter_type, MeterType::Unknown(42));
+        assert!((result.battery_voltage_v.unwrap() - 12.50).abs() < f32::EPSILON);
+        assert_eq!(result.alarm_reason.bits(), 0x4000);
+        assert_eq!(result.aux_input, AuxInput::None);
+        assert!((result.battery_current_a.unwrap() + 2.5).abs() < 0.001);
+        assert!(result.has_unknown_values());
+        assert_eq!(result.encode().unwrap(), test_data);
+    }
+}
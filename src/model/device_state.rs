@@ -3,7 +3,16 @@ use crate::record::*;
 
 use super::ac_charger_state::AcChargerState;
 use super::battery_monitor_state::BatteryMonitorState;
+use super::dc_dc_converter_state::DcDcConverterState;
+use super::dc_energy_meter_state::DcEnergyMeterState;
+use super::gx_device_state::GxDeviceState;
+use super::inverter_rs_state::InverterRsState;
 use super::inverter_state::InverterState;
+use super::lynx_smart_bms_state::LynxSmartBmsState;
+use super::multi_rs_state::MultiRsState;
+use super::orion_xs_state::OrionXsState;
+use super::smart_battery_protect_state::SmartBatteryProtectState;
+use super::smart_lithium_state::SmartLithiumState;
 use super::solar_charger_state::SolarChargerState;
 use super::test_record_state::TestRecordState;
 use super::ve_bus_state::VeBusState;
@@ -17,6 +26,22 @@ pub enum DeviceState {
     BatteryMonitor(BatteryMonitorState),
     Inverter(InverterState),
     VeBus(VeBusState),
+    DcDcConverter(DcDcConverterState),
+    SmartLithium(SmartLithiumState),
+    InverterRs(InverterRsState),
+    GxDevice(GxDeviceState),
+    SmartBatteryProtect(SmartBatteryProtectState),
+    LynxSmartBms(LynxSmartBmsState),
+    MultiRs(MultiRsState),
+    DcEnergyMeter(DcEnergyMeterState),
+    OrionXs(OrionXsState),
+    /// A record type that this crate does not decode yet. The decrypted payload is
+    /// provided so that it can be decoded by the user.
+    Unknown {
+        record_type: u8,
+        product_id: u16,
+        payload: [u8; 16],
+    },
 }
 
 impl DeviceState {
@@ -34,7 +59,383 @@ impl DeviceState {
             RECORD_TYPE_INVERTER => Ok(Self::Inverter(InverterState::parse(&record.decrypt()?)?)),
             RECORD_TYPE_VE_BUS => Ok(Self::VeBus(VeBusState::parse(&record.decrypt()?)?)),
             RECORD_TYPE_AC_CHARGER => Ok(Self::AcCharger(AcChargerState::parse(&record.decrypt()?)?)),
-            _ => Err(Error::UnsupportedDeviceType(record.record_type())),
+            RECORD_TYPE_DC_DC_CONVERTER => Ok(Self::DcDcConverter(DcDcConverterState::parse(
+                &record.decrypt()?,
+            )?)),
+            RECORD_TYPE_SMART_LITHIUM => Ok(Self::SmartLithium(SmartLithiumState::parse(
+                &record.decrypt()?,
+            )?)),
+            RECORD_TYPE_INVERTER_RS => Ok(Self::InverterRs(InverterRsState::parse(
+                &record.decrypt()?,
+            )?)),
+            RECORD_TYPE_GX_DEVICE => Ok(Self::GxDevice(GxDeviceState::parse(&record.decrypt()?)?)),
+            RECORD_TYPE_SMART_BATTERY_PROTECT => Ok(Self::SmartBatteryProtect(
+                SmartBatteryProtectState::parse(&record.decrypt()?)?,
+            )),
+            RECORD_TYPE_LYNX_SMART_BMS => Ok(Self::LynxSmartBms(LynxSmartBmsState::parse(
+                &record.decrypt()?,
+            )?)),
+            RECORD_TYPE_MULTI_RS => Ok(Self::MultiRs(MultiRsState::parse(&record.decrypt()?)?)),
+            RECORD_TYPE_DC_ENERGY_METER => Ok(Self::DcEnergyMeter(DcEnergyMeterState::parse(
+                &record.decrypt()?,
+            )?)),
+            RECORD_TYPE_ORION_XS => Ok(Self::OrionXs(OrionXsState::parse(&record.decrypt()?)?)),
+            _ => Ok(Self::Unknown {
+                record_type: record.record_type(),
+                product_id: record.header().product_id,
+                payload: record.decrypt()?,
+            }),
+        }
+    }
+
+    /// The record type that identifies this kind of device state in the manufacturer data.
+    pub fn record_type(&self) -> u8 {
+        match self {
+            Self::TestRecord(_) => RECORD_TYPE_TEST_RECORD,
+            Self::SolarCharger(_) => RECORD_TYPE_SOLAR_CHARGER,
+            Self::AcCharger(_) => RECORD_TYPE_AC_CHARGER,
+            Self::BatteryMonitor(_) => RECORD_TYPE_BATTERY_MONITOR,
+            Self::Inverter(_) => RECORD_TYPE_INVERTER,
+            Self::VeBus(_) => RECORD_TYPE_VE_BUS,
+            Self::DcDcConverter(_) => RECORD_TYPE_DC_DC_CONVERTER,
+            Self::SmartLithium(_) => RECORD_TYPE_SMART_LITHIUM,
+            Self::InverterRs(_) => RECORD_TYPE_INVERTER_RS,
+            Self::GxDevice(_) => RECORD_TYPE_GX_DEVICE,
+            Self::SmartBatteryProtect(_) => RECORD_TYPE_SMART_BATTERY_PROTECT,
+            Self::LynxSmartBms(_) => RECORD_TYPE_LYNX_SMART_BMS,
+            Self::MultiRs(_) => RECORD_TYPE_MULTI_RS,
+            Self::DcEnergyMeter(_) => RECORD_TYPE_DC_ENERGY_METER,
+            Self::OrionXs(_) => RECORD_TYPE_ORION_XS,
+            Self::Unknown { record_type, .. } => *record_type,
         }
     }
+
+    /// Whether the state holds a value that is not known to this version of the crate,
+    /// such as a mode or error code added in newer firmware. The rest of the state is
+    /// still valid. An unknown record type always counts as holding unknown values.
+    pub fn has_unknown_values(&self) -> bool {
+        match self {
+            Self::TestRecord(_) => false,
+            Self::SolarCharger(state) => state.has_unknown_values(),
+            Self::AcCharger(state) => state.has_unknown_values(),
+            Self::BatteryMonitor(state) => state.has_unknown_values(),
+            Self::Inverter(state) => state.has_unknown_values(),
+            Self::VeBus(state) => state.has_unknown_values(),
+            Self::DcDcConverter(state) => state.has_unknown_values(),
+            Self::SmartLithium(state) => state.has_unknown_values(),
+            Self::InverterRs(state) => state.has_unknown_values(),
+            Self::GxDevice(_) => false,
+            Self::SmartBatteryProtect(state) => state.has_unknown_values(),
+            Self::LynxSmartBms(_) => false,
+            Self::MultiRs(state) => state.has_unknown_values(),
+            Self::DcEnergyMeter(state) => state.has_unknown_values(),
+            Self::OrionXs(state) => state.has_unknown_values(),
+            Self::Unknown { .. } => true,
+        }
+    }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        match self {
+            Self::TestRecord(state) => state.encode(),
+            Self::SolarCharger(state) => state.encode(),
+            Self::AcCharger(state) => state.encode(),
+            Self::BatteryMonitor(state) => state.encode(),
+            Self::Inverter(state) => state.encode(),
+            Self::VeBus(state) => state.encode(),
+            Self::DcDcConverter(state) => state.encode(),
+            Self::SmartLithium(state) => state.encode(),
+            Self::InverterRs(state) => state.encode(),
+            Self::GxDevice(state) => state.encode(),
+            Self::SmartBatteryProtect(state) => state.encode(),
+            Self::LynxSmartBms(state) => state.encode(),
+            Self::MultiRs(state) => state.encode(),
+            Self::DcEnergyMeter(state) => state.encode(),
+            Self::OrionXs(state) => state.encode(),
+            Self::Unknown { payload, .. } => Ok(*payload),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Decoder, EncryptionKey, ProductFamily};
+    use aes::cipher::StreamCipher;
+    use ctr::cipher::KeyIvInit;
+
+    #[test]
+    fn test_parse_unknown_record_type() {
+        let payload = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let key = [
+            17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
+        ];
+        let iv = [33, 34, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let mut algo = ctr::Ctr128LE::<aes::Aes128>::new(key.as_slice().into(), &iv.into());
+        let mut cipher = [0; 16];
+        algo.apply_keystream_b2b(&payload, &mut cipher).unwrap();
+
+        let mut manufacturer_data = [0; 24];
+        manufacturer_data[..8]
+            .copy_from_slice(&[0x10, 0x00, 0x34, 0x12, 0xEE, iv[0], iv[1], key[0]]);
+        manufacturer_data[8..].copy_from_slice(&cipher);
+        let decoder = Decoder::new(&EncryptionKey::new(key));
+        let record = Record::new(&manufacturer_data, &decoder).unwrap();
+
+        let result = DeviceState::parse(&record).unwrap();
+
+        assert_eq!(
+            result,
+            DeviceState::Unknown {
+                record_type: 0xEE,
+                product_id: 0x1234,
+                payload,
+            }
+        );
+    }
+
+    fn key() -> EncryptionKey {
+        EncryptionKey::new([
+            17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
+        ])
+    }
+
+    fn decoder() -> Decoder {
+        Decoder::new(&key())
+    }
+
+    fn parse_payload(record_type: u8, payload: &[u8; 16]) -> DeviceState {
+        let header = crate::AdvertisementHeader {
+            product_id: 0xA389,
+            record_type,
+            nonce: 0x2221,
+            key_check: 17,
+        };
+        let decoder = decoder();
+        let manufacturer_data = encrypt(&header, payload, &decoder).unwrap();
+        DeviceState::parse(&Record::new(&manufacturer_data, &decoder).unwrap()).unwrap()
+    }
+
+    /// A valid payload of every record type, and of a record type unknown to the crate.
+    const PAYLOADS: [(u8, [u8; 16]); 18] = [
+        (
+            RECORD_TYPE_TEST_RECORD,
+            [
+                0x10, 0x0E, 0x00, 0x40, 0xF0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+                0xFF, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_SOLAR_CHARGER,
+            [
+                0x03, 0x00, 0x28, 0x05, 0x32, 0x00, 0x7D, 0x00, 0x48, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+                0xFF, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_BATTERY_MONITOR,
+            [
+                0x58, 0x02, 0xE2, 0x04, 0x00, 0x00, 0x77, 0x74, 0xF2, 0xD8, 0xFF, 0x7B, 0x00, 0xB0,
+                0xF6, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_BATTERY_MONITOR,
+            [
+                0x58, 0x02, 0xE2, 0x04, 0x00, 0x00, 0x77, 0x74, 0xFE, 0xFF, 0xFF, 0x7B, 0x00, 0xB0,
+                0xF6, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_INVERTER,
+            [
+                0x09, 0x00, 0x00, 0xE2, 0x04, 0x96, 0x00, 0xD8, 0xD9, 0x03, 0xFC, 0xFF, 0xFF, 0xFF,
+                0xFF, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_DC_DC_CONVERTER,
+            [
+                0x04, 0x00, 0x6B, 0x05, 0x8C, 0x05, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+                0xFF, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_SMART_LITHIUM,
+            [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC8, 0x24, 0x52, 0xF9, 0xFF, 0xFF, 0xFF, 0x38,
+                0x15, 0xBE,
+            ],
+        ),
+        (
+            RECORD_TYPE_INVERTER_RS,
+            [
+                0x09, 0x00, 0x5A, 0x0A, 0x85, 0xFF, 0x9C, 0x01, 0xBB, 0x00, 0x20, 0x01, 0xFF, 0xFF,
+                0xFF, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_GX_DEVICE,
+            [
+                0x5A, 0x0A, 0x3A, 0x07, 0x70, 0xE5, 0xF2, 0xFF, 0xF6, 0x04, 0xE0, 0xFF, 0xFF, 0xFF,
+                0xFF, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_AC_CHARGER,
+            [
+                0x04, 0x00, 0xA0, 0x05, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x1B,
+                0xB9, 0x2F,
+            ],
+        ),
+        (
+            RECORD_TYPE_SMART_BATTERY_PROTECT,
+            [
+                0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x28, 0x05, 0x24, 0x05, 0x00, 0x00, 0x00,
+                0x00, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_LYNX_SMART_BMS,
+            [
+                0x00, 0xA0, 0x05, 0x5A, 0x0A, 0x29, 0xFF, 0x00, 0x00, 0x00, 0x00, 0xA4, 0x1D, 0x11,
+                0x00, 0xBB,
+            ],
+        ),
+        (
+            RECORD_TYPE_MULTI_RS,
+            [
+                0x09, 0x00, 0xAC, 0xFF, 0x00, 0x94, 0x00, 0x00, 0x5E, 0x06, 0x9C, 0x04, 0x9C, 0x01,
+                0xFF, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_VE_BUS,
+            [
+                0x05, 0x00, 0x16, 0x00, 0x46, 0x05, 0x2f, 0x00, 0x00, 0x00, 0x00, 0xc2, 0xff, 0xc1,
+                0x16, 0x11,
+            ],
+        ),
+        (
+            RECORD_TYPE_DC_ENERGY_METER,
+            [
+                0xFA, 0xFF, 0x48, 0x05, 0x00, 0x00, 0x77, 0x74, 0xBA, 0x95, 0x02, 0xFF, 0xFF, 0xFF,
+                0xFF, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_DC_ENERGY_METER,
+            [
+                0x03, 0x00, 0xE8, 0x04, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+                0xFF, 0xFF,
+            ],
+        ),
+        (
+            RECORD_TYPE_ORION_XS,
+            [
+                0x03, 0x00, 0x8C, 0x05, 0x2C, 0x01, 0x46, 0x05, 0x54, 0x01, 0x00, 0x00, 0x00, 0x00,
+                0xFF, 0xFF,
+            ],
+        ),
+        (
+            0xEE,
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+        ),
+    ];
+
+    #[test]
+    fn test_encode_round_trip() {
+        for (record_type, payload) in PAYLOADS {
+            let device_state = parse_payload(record_type, &payload);
+            assert_eq!(device_state.record_type(), record_type);
+
+            let manufacturer_data =
+                crate::encode_manufacturer_data(&device_state, 0xA389, 0x2221, &key()).unwrap();
+            let (header, result) =
+                crate::parse_manufacturer_data_with_header(&manufacturer_data, &key()).unwrap();
+
+            assert_eq!(header.product_id, 0xA389);
+            assert_eq!(header.record_type, record_type);
+            assert_eq!(header.nonce, 0x2221);
+            assert_eq!(result, device_state);
+        }
+    }
+
+    #[test]
+    fn test_record_types_resolve_to_product() {
+        let products = [
+            (
+                RECORD_TYPE_SOLAR_CHARGER,
+                0xA055,
+                ProductFamily::SolarCharger,
+            ),
+            (
+                RECORD_TYPE_BATTERY_MONITOR,
+                0xA389,
+                ProductFamily::BatteryMonitor,
+            ),
+            (RECORD_TYPE_INVERTER, 0xA201, ProductFamily::Inverter),
+            (
+                RECORD_TYPE_DC_DC_CONVERTER,
+                0xA3C0,
+                ProductFamily::DcDcConverter,
+            ),
+            (
+                RECORD_TYPE_SMART_LITHIUM,
+                0xA3E0,
+                ProductFamily::SmartLithium,
+            ),
+            (RECORD_TYPE_INVERTER_RS, 0xA2FF, ProductFamily::InverterRs),
+            (RECORD_TYPE_GX_DEVICE, 0xA160, ProductFamily::GxDevice),
+            (RECORD_TYPE_AC_CHARGER, 0xA330, ProductFamily::AcCharger),
+            (
+                RECORD_TYPE_SMART_BATTERY_PROTECT,
+                0xA3D0,
+                ProductFamily::SmartBatteryProtect,
+            ),
+            (
+                RECORD_TYPE_LYNX_SMART_BMS,
+                0xA3E5,
+                ProductFamily::LynxSmartBms,
+            ),
+            (RECORD_TYPE_MULTI_RS, 0xA442, ProductFamily::MultiRs),
+            (
+                RECORD_TYPE_DC_ENERGY_METER,
+                0xA389,
+                ProductFamily::BatteryMonitor,
+            ),
+            (RECORD_TYPE_ORION_XS, 0xA3F0, ProductFamily::DcDcConverter),
+        ];
+
+        for (record_type, product_id, family) in products {
+            let (_, payload) = PAYLOADS.iter().find(|(t, _)| *t == record_type).unwrap();
+            let device_state = parse_payload(record_type, payload);
+
+            let manufacturer_data =
+                crate::encode_manufacturer_data(&device_state, product_id, 0x2221, &key()).unwrap();
+            let (header, _) =
+                crate::parse_manufacturer_data_with_header(&manufacturer_data, &key()).unwrap();
+
+            let product = header.product().unwrap();
+            assert_eq!(product.id, product_id);
+            assert_eq!(product.family, family, "record type {record_type:#04X}");
+        }
+    }
+
+    #[test]
+    fn test_encode_value_out_of_range() {
+        let mut device_state = parse_payload(
+            RECORD_TYPE_DC_DC_CONVERTER,
+            &[
+                0x04, 0x00, 0x6B, 0x05, 0x8C, 0x05, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+                0xFF, 0xFF,
+            ],
+        );
+        if let DeviceState::DcDcConverter(state) = &mut device_state {
+            state.input_voltage_v = Some(1000.0);
+        }
+
+        let result = crate::encode_manufacturer_data(&device_state, 0xA389, 0x2221, &key());
+
+        assert!(matches!(result, Err(Error::ValueOutOfRange)));
+    }
 }
@@ -1,9 +1,11 @@
-use num_enum::TryFromPrimitive;
+use num_enum::{FromPrimitive, IntoPrimitive};
 use strum::Display;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Eq, PartialEq, TryFromPrimitive, Copy, Clone, Ord, PartialOrd, Hash, Display)]
-#[repr(u64)]
+#[derive(
+    Debug, Eq, PartialEq, FromPrimitive, IntoPrimitive, Copy, Clone, Ord, PartialOrd, Hash, Display,
+)]
+#[repr(u8)]
 pub enum ErrorState {
     NotApplicable = 0xFF,
     NoError = 0,
@@ -26,4 +28,7 @@ pub enum ErrorState {
     FactoryCalibrationDataLost = 116,
     InvalidFirmware = 117,
     UserSettingsInvalid = 119,
+    /// An error state that is not known to this version of the crate, holding the raw value.
+    #[num_enum(catch_all)]
+    Unknown(u8),
 }
@@ -0,0 +1,89 @@
+use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
+use crate::err::*;
+
+/// System summary broadcast by a GX device such as the Cerbo GX.
+///
+/// Victron mark this record layout as still to be determined so it may change.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct GxDeviceState {
+    pub battery_voltage_v: Option<f32>,
+    pub pv_power_w: Option<f32>,
+    pub soc_percent: Option<f32>,
+    pub battery_power_w: Option<f32>,
+    pub dc_power_w: Option<f32>,
+}
+
+impl GxDeviceState {
+    pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(payload);
+
+        let battery_voltage_v = reader.read_unsigned_field(16, 0xFFFF, 0.01, 0.0)?;
+        let pv_power_w = reader.read_unsigned_field(20, 0xFFFFF, 1.0, 0.0)?;
+        let soc_percent = reader.read_unsigned_field(7, 0x7F, 1.0, 0.0)?;
+        let battery_power_w = reader.read_signed_field(21, 0x0FFFFF, 1.0)?;
+        let dc_power_w = reader.read_signed_field(21, 0x0FFFFF, 1.0)?;
+
+        Ok(Self {
+            battery_voltage_v,
+            pv_power_w,
+            soc_percent,
+            battery_power_w,
+            dc_power_w,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_field(16, 0xFFFF, 0.01, 0.0, self.battery_voltage_v)?;
+        writer.write_unsigned_field(20, 0xFFFFF, 1.0, 0.0, self.pv_power_w)?;
+        writer.write_unsigned_field(7, 0x7F, 1.0, 0.0, self.soc_percent)?;
+        writer.write_signed_field(21, 0x0FFFFF, 1.0, self.battery_power_w)?;
+        writer.write_signed_field(21, 0x0FFFFF, 1.0, self.dc_power_w)?;
+
+        Ok(writer.finish())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Raw: [0x5A, 0x0A, 0x3A, 0x07, 0x70, 0xE5, 0xF2, 0xFF, 0xF6, 0x04, 0xE0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    // 24V site with PV covering the DC loads and the surplus going into the battery
+    #[test]
+    fn test_gx_device_state_parse_1() {
+        let test_data = [
+            0x5A, 0x0A, 0x3A, 0x07, 0x70, 0xE5, 0xF2, 0xFF, 0xF6, 0x04, 0xE0, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = GxDeviceState::parse(&test_data).unwrap();
+
+        assert!((result.battery_voltage_v.unwrap() - 26.50).abs() < f32::EPSILON);
+        assert!((result.pv_power_w.unwrap() - 1850.0).abs() < f32::EPSILON);
+        assert!((result.soc_percent.unwrap() - 87.0).abs() < f32::EPSILON);
+        assert!((result.battery_power_w.unwrap() + 420.0).abs() < f32::EPSILON);
+        assert!((result.dc_power_w.unwrap() - 1270.0).abs() < f32::EPSILON);
+    }
+
+    // Raw: [0xFF, 0xFF, 0x00, 0x00, 0xF0, 0xFF, 0xFF, 0x7F, 0xFF, 0xFF, 0xEF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    // GX device without a battery monitor, at night
+    #[test]
+    fn test_gx_device_state_parse_2() {
+        let test_data = [
+            0xFF, 0xFF, 0x00, 0x00, 0xF0, 0xFF, 0xFF, 0x7F, 0xFF, 0xFF, 0xEF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = GxDeviceState::parse(&test_data).unwrap();
+
+        assert!(result.battery_voltage_v.is_none());
+        assert!((result.pv_power_w.unwrap() - 0.0).abs() < f32::EPSILON);
+        assert!(result.soc_percent.is_none());
+        assert!(result.battery_power_w.is_none());
+        assert!(result.dc_power_w.is_none());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
+use crate::err::*;
+
+use super::error_state::ErrorState;
+use super::mode::Mode;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct InverterRsState {
+    pub mode: Mode,
+    pub error_state: ErrorState,
+    pub battery_voltage_v: Option<f32>,
+    pub battery_current_a: Option<f32>,
+    pub pv_power_w: Option<f32>,
+    pub yield_today_kwh: Option<f32>,
+    pub ac_out_power_w: Option<f32>,
+}
+
+impl InverterRsState {
+    pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(payload);
+
+        let mode = Mode::from(reader.read_unsigned_int(8)? as u8);
+        let error_state = ErrorState::from(reader.read_unsigned_int(8)? as u8);
+        let battery_voltage_v = reader.read_signed_field(16, 0x7FFF, 0.01)?;
+        let battery_current_a = reader.read_signed_field(16, 0x7FFF, 0.1)?;
+        let pv_power_w = reader.read_unsigned_field(16, 0xFFFF, 1.0, 0.0)?;
+        let yield_today_kwh = reader.read_unsigned_field(16, 0xFFFF, 0.01, 0.0)?;
+        let ac_out_power_w = reader.read_signed_field(16, 0x7FFF, 1.0)?;
+
+        Ok(Self {
+            mode,
+            error_state,
+            battery_voltage_v,
+            battery_current_a,
+            pv_power_w,
+            yield_today_kwh,
+            ac_out_power_w,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(8, u8::from(self.mode) as u64)?;
+        writer.write_unsigned_int(8, u8::from(self.error_state) as u64)?;
+        writer.write_signed_field(16, 0x7FFF, 0.01, self.battery_voltage_v)?;
+        writer.write_signed_field(16, 0x7FFF, 0.1, self.battery_current_a)?;
+        writer.write_unsigned_field(16, 0xFFFF, 1.0, 0.0, self.pv_power_w)?;
+        writer.write_unsigned_field(16, 0xFFFF, 0.01, 0.0, self.yield_today_kwh)?;
+        writer.write_signed_field(16, 0x7FFF, 1.0, self.ac_out_power_w)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        matches!(self.mode, Mode::Unknown(_)) || matches!(self.error_state, ErrorState::Unknown(_))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Raw: [0x09, 0x00, 0x5A, 0x0A, 0x85, 0xFF, 0x9C, 0x01, 0xBB, 0x00, 0x20, 0x01, 0xFF, 0xFF, 0xFF, 0xFF]
+    // Inverting from a 24V battery while the PV array supplies part of the load
+    #[test]
+    fn test_inverter_rs_state_parse_1() {
+        let test_data = [
+            0x09, 0x00, 0x5A, 0x0A, 0x85, 0xFF, 0x9C, 0x01, 0xBB, 0x00, 0x20, 0x01, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = InverterRsState::parse(&test_data).unwrap();
+
+        assert_eq!(result.mode, Mode::Inverting);
+        assert_eq!(result.error_state, ErrorState::NoError);
+        assert!((result.battery_voltage_v.unwrap() - 26.50).abs() < f32::EPSILON);
+        assert!((result.battery_current_a.unwrap() + 12.3).abs() < 0.001);
+        assert!((result.pv_power_w.unwrap() - 412.0).abs() < f32::EPSILON);
+        assert!((result.yield_today_kwh.unwrap() - 1.87).abs() < f32::EPSILON);
+        assert!((result.ac_out_power_w.unwrap() - 288.0).abs() < f32::EPSILON);
+    }
+
+    // Raw: [0x03, 0x00, 0x50, 0x0A, 0x9B, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F, 0xFF, 0xFF, 0xFF, 0xFF]
+    // Bulk charging with the PV and AC output fields not available
+    #[test]
+    fn test_inverter_rs_state_parse_2() {
+        let test_data = [
+            0x03, 0x00, 0x50, 0x0A, 0x9B, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = InverterRsState::parse(&test_data).unwrap();
+
+        assert_eq!(result.mode, Mode::Bulk);
+        assert_eq!(result.error_state, ErrorState::NoError);
+        assert!((result.battery_voltage_v.unwrap() - 26.40).abs() < f32::EPSILON);
+        assert!((result.battery_current_a.unwrap() - 15.5).abs() < 0.001);
+        assert!(result.pv_power_w.is_none());
+        assert!(result.yield_today_kwh.is_none());
+        assert!(result.ac_out_power_w.is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use super::alarm_reason::AlarmReason;
 use super::Mode;
 use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
 use crate::err::*;
 
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
@@ -18,9 +19,8 @@ impl InverterState {
     pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
         let mut reader = BitReader::new(payload);
 
-        let mode = Mode::try_from(reader.read_unsigned_int(8)?)?;
-        let alarm_reason =
-            AlarmReason::from_bits(reader.read_signed_int(16)?).ok_or(Error::InvalidAlarmReason)?;
+        let mode = Mode::from(reader.read_unsigned_int(8)? as u8);
+        let alarm_reason = AlarmReason::from_bits_retain(reader.read_signed_int(16)?);
         let battery_voltage_v = reader.read_signed_field(16, 0x7FFF, 0.01)?;
         let ac_apparent_power_va = reader.read_unsigned_field(16, 0xFFFF, 1.0, 0.0)?;
         let ac_voltage_v = reader.read_unsigned_field(15, 0x7FFF, 0.01, 0.0)?;
@@ -35,4 +35,23 @@ impl InverterState {
             ac_current_a,
         })
     }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(8, u8::from(self.mode) as u64)?;
+        writer.write_signed_int(16, self.alarm_reason.bits())?;
+        writer.write_signed_field(16, 0x7FFF, 0.01, self.battery_voltage_v)?;
+        writer.write_unsigned_field(16, 0xFFFF, 1.0, 0.0, self.ac_apparent_power_va)?;
+        writer.write_unsigned_field(15, 0x7FFF, 0.01, 0.0, self.ac_voltage_v)?;
+        writer.write_unsigned_field(11, 0x7FF, 0.1, 0.0, self.ac_current_a)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        matches!(self.mode, Mode::Unknown(_))
+            || AlarmReason::from_bits(self.alarm_reason.bits()).is_none()
+    }
 }
@@ -0,0 +1,108 @@
+use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
+use crate::err::*;
+
+/// The field names shared with `BatteryMonitorState` have the same meaning and units.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LynxSmartBmsState {
+    pub error: u8,
+    pub time_to_go_mins: Option<f32>,
+    pub battery_voltage_v: Option<f32>,
+    pub battery_current_a: Option<f32>,
+    pub io_status: u16,
+    pub warnings_alarms: u32,
+    pub state_of_charge_pct: Option<f32>,
+    pub consumed_amp_hours_ah: Option<f32>,
+    pub battery_temperature_c: Option<f32>,
+}
+
+impl LynxSmartBmsState {
+    pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(payload);
+
+        let error = reader.read_unsigned_int(8)? as u8;
+        let time_to_go_mins = reader.read_unsigned_field(16, 0xFFFF, 1.0, 0.0)?;
+        let battery_voltage_v = reader.read_signed_field(16, 0x7FFF, 0.01)?;
+        let battery_current_a = reader.read_signed_field(16, 0x7FFF, 0.1)?;
+        let io_status = reader.read_unsigned_int(16)? as u16;
+        let warnings_alarms = reader.read_unsigned_int(18)? as u32;
+        let state_of_charge_pct = reader.read_unsigned_field(10, 0x3FF, 0.1, 0.0)?;
+        let consumed_amp_hours_ah = reader.read_unsigned_field(20, 0xFFFFF, -0.1, 0.0)?;
+        let battery_temperature_c = reader.read_unsigned_field(7, 0x7F, 1.0, -40.0)?;
+
+        Ok(Self {
+            error,
+            time_to_go_mins,
+            battery_voltage_v,
+            battery_current_a,
+            io_status,
+            warnings_alarms,
+            state_of_charge_pct,
+            consumed_amp_hours_ah,
+            battery_temperature_c,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(8, self.error as u64)?;
+        writer.write_unsigned_field(16, 0xFFFF, 1.0, 0.0, self.time_to_go_mins)?;
+        writer.write_signed_field(16, 0x7FFF, 0.01, self.battery_voltage_v)?;
+        writer.write_signed_field(16, 0x7FFF, 0.1, self.battery_current_a)?;
+        writer.write_unsigned_int(16, self.io_status as u64)?;
+        writer.write_unsigned_int(18, self.warnings_alarms as u64)?;
+        writer.write_unsigned_field(10, 0x3FF, 0.1, 0.0, self.state_of_charge_pct)?;
+        writer.write_unsigned_field(20, 0xFFFFF, -0.1, 0.0, self.consumed_amp_hours_ah)?;
+        writer.write_unsigned_field(7, 0x7F, 1.0, -40.0, self.battery_temperature_c)?;
+
+        Ok(writer.finish())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Raw: [0x00, 0xA0, 0x05, 0x5A, 0x0A, 0x29, 0xFF, 0x00, 0x00, 0x00, 0x00, 0xA4, 0x1D, 0x11, 0x00, 0xBB]
+    // 24V bank discharging
+    #[test]
+    fn test_lynx_smart_bms_state_parse_1() {
+        let test_data = [
+            0x00, 0xA0, 0x05, 0x5A, 0x0A, 0x29, 0xFF, 0x00, 0x00, 0x00, 0x00, 0xA4, 0x1D, 0x11,
+            0x00, 0xBB,
+        ];
+
+        let result = LynxSmartBmsState::parse(&test_data).unwrap();
+
+        assert_eq!(result.error, 0);
+        assert!((result.time_to_go_mins.unwrap() - 1440.0).abs() < f32::EPSILON);
+        assert!((result.battery_voltage_v.unwrap() - 26.50).abs() < f32::EPSILON);
+        assert!((result.battery_current_a.unwrap() + 21.5).abs() < 0.001);
+        assert_eq!(result.io_status, 0);
+        assert_eq!(result.warnings_alarms, 0);
+        assert!((result.state_of_charge_pct.unwrap() - 87.3).abs() < 0.001);
+        assert!((result.consumed_amp_hours_ah.unwrap() + 27.3).abs() < 0.001);
+        assert!((result.battery_temperature_c.unwrap() - 19.0).abs() < f32::EPSILON);
+    }
+
+    // Raw: [0x00, 0xFF, 0xFF, 0xFF, 0x7F, 0xFF, 0x7F, 0x00, 0x00, 0x00, 0x00, 0xFC, 0xFF, 0xFF, 0xFF, 0xFF]
+    // Freshly started BMS that has not measured anything yet
+    #[test]
+    fn test_lynx_smart_bms_state_parse_2() {
+        let test_data = [
+            0x00, 0xFF, 0xFF, 0xFF, 0x7F, 0xFF, 0x7F, 0x00, 0x00, 0x00, 0x00, 0xFC, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = LynxSmartBmsState::parse(&test_data).unwrap();
+
+        assert!(result.time_to_go_mins.is_none());
+        assert!(result.battery_voltage_v.is_none());
+        assert!(result.battery_current_a.is_none());
+        assert!(result.state_of_charge_pct.is_none());
+        assert!(result.consumed_amp_hours_ah.is_none());
+        assert!(result.battery_temperature_c.is_none());
+    }
+}
@@ -1,20 +1,44 @@
+mod advertisement_header;
 mod alarm_reason;
 mod battery_monitor_state;
+mod dc_dc_converter_state;
+mod dc_energy_meter_state;
 mod device_state;
 mod error_state;
+mod gx_device_state;
+mod inverter_rs_state;
 mod inverter_state;
+mod lynx_smart_bms_state;
 mod mode;
+mod multi_rs_state;
+mod off_reason;
+mod orion_xs_state;
+mod product;
+mod smart_battery_protect_state;
+mod smart_lithium_state;
 mod solar_charger_state;
 mod ac_charger_state;
 mod test_record_state;
 mod ve_bus_state;
 
+pub use advertisement_header::AdvertisementHeader;
 pub use alarm_reason::AlarmReason;
-pub use battery_monitor_state::BatteryMonitorState;
+pub use battery_monitor_state::{AuxInput, BatteryMonitorState};
+pub use dc_dc_converter_state::DcDcConverterState;
+pub use dc_energy_meter_state::*;
 pub use device_state::DeviceState;
 pub use error_state::ErrorState;
+pub use gx_device_state::GxDeviceState;
+pub use inverter_rs_state::InverterRsState;
 pub use inverter_state::InverterState;
+pub use lynx_smart_bms_state::LynxSmartBmsState;
 pub use mode::Mode;
+pub use multi_rs_state::MultiRsState;
+pub use off_reason::OffReason;
+pub use orion_xs_state::OrionXsState;
+pub use product::*;
+pub use smart_battery_protect_state::*;
+pub use smart_lithium_state::*;
 pub use solar_charger_state::SolarChargerState;
 pub use test_record_state::TestRecordState;
 pub use ve_bus_state::*;
@@ -1,9 +1,11 @@
-use num_enum::TryFromPrimitive;
+use num_enum::{FromPrimitive, IntoPrimitive};
 use strum::Display;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Eq, PartialEq, TryFromPrimitive, Copy, Clone, Ord, PartialOrd, Hash, Display)]
-#[repr(u64)]
+#[derive(
+    Debug, Eq, PartialEq, FromPrimitive, IntoPrimitive, Copy, Clone, Ord, PartialOrd, Hash, Display,
+)]
+#[repr(u8)]
 pub enum Mode {
     NotApplicable = 0xFF,
     Off = 0,
@@ -21,4 +23,7 @@ pub enum Mode {
     AutoEqualize = 247,
     BatterySafe = 248,
     ExternalControl = 252,
+    /// A mode that is not known to this version of the crate, holding the raw value.
+    #[num_enum(catch_all)]
+    Unknown(u8),
 }
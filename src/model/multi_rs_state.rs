@@ -0,0 +1,119 @@
+use super::error_state::ErrorState;
+use super::mode::Mode;
+use super::ve_bus_state::AcInState;
+use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
+use crate::err::*;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct MultiRsState {
+    pub mode: Mode,
+    pub error: ErrorState,
+    pub battery_current_a: Option<f32>,
+    pub battery_voltage_v: Option<f32>,
+    pub ac_in_state: AcInState,
+    pub ac_in_power_w: Option<f32>,
+    pub ac_out_power_w: Option<f32>,
+    pub pv_power_w: Option<f32>,
+    pub yield_today_kwh: Option<f32>,
+}
+
+impl MultiRsState {
+    pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(payload);
+
+        let mode = Mode::from(reader.read_unsigned_int(8)? as u8);
+        let error = ErrorState::from(reader.read_unsigned_int(8)? as u8);
+        let battery_current_a = reader.read_signed_field(16, 0x7FFF, 0.1)?;
+        let battery_voltage_v = reader.read_unsigned_field(14, 0x3FFF, 0.01, 0.0)?;
+        let ac_in_state = AcInState::try_from(reader.read_unsigned_int(2)? as u8)
+            .ok()
+            .ok_or(Error::InvalidAcInState)?;
+        let ac_in_power_w = reader.read_signed_field(16, 0x7FFF, 1.0)?;
+        let ac_out_power_w = reader.read_signed_field(16, 0x7FFF, 1.0)?;
+        let pv_power_w = reader.read_unsigned_field(16, 0xFFFF, 1.0, 0.0)?;
+        let yield_today_kwh = reader.read_unsigned_field(16, 0xFFFF, 0.01, 0.0)?;
+
+        Ok(Self {
+            mode,
+            error,
+            battery_current_a,
+            battery_voltage_v,
+            ac_in_state,
+            ac_in_power_w,
+            ac_out_power_w,
+            pv_power_w,
+            yield_today_kwh,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(8, u8::from(self.mode) as u64)?;
+        writer.write_unsigned_int(8, u8::from(self.error) as u64)?;
+        writer.write_signed_field(16, 0x7FFF, 0.1, self.battery_current_a)?;
+        writer.write_unsigned_field(14, 0x3FFF, 0.01, 0.0, self.battery_voltage_v)?;
+        writer.write_unsigned_int(2, self.ac_in_state as u64)?;
+        writer.write_signed_field(16, 0x7FFF, 1.0, self.ac_in_power_w)?;
+        writer.write_signed_field(16, 0x7FFF, 1.0, self.ac_out_power_w)?;
+        writer.write_unsigned_field(16, 0xFFFF, 1.0, 0.0, self.pv_power_w)?;
+        writer.write_unsigned_field(16, 0xFFFF, 0.01, 0.0, self.yield_today_kwh)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        matches!(self.mode, Mode::Unknown(_)) || matches!(self.error, ErrorState::Unknown(_))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Raw: [0x09, 0x00, 0xAC, 0xFF, 0x00, 0x94, 0x00, 0x00, 0x5E, 0x06, 0x9C, 0x04, 0x9C, 0x01, 0xFF, 0xFF]
+    // Inverting off-grid, PV covering most of the load
+    #[test]
+    fn test_multi_rs_state_parse_1() {
+        let test_data = [
+            0x09, 0x00, 0xAC, 0xFF, 0x00, 0x94, 0x00, 0x00, 0x5E, 0x06, 0x9C, 0x04, 0x9C, 0x01,
+            0xFF, 0xFF,
+        ];
+
+        let result = MultiRsState::parse(&test_data).unwrap();
+
+        assert_eq!(result.mode, Mode::Inverting);
+        assert_eq!(result.error, ErrorState::NoError);
+        assert!((result.battery_current_a.unwrap() + 8.4).abs() < 0.001);
+        assert!((result.battery_voltage_v.unwrap() - 51.20).abs() < 0.001);
+        assert_eq!(result.ac_in_state, AcInState::NotConnected);
+        assert!((result.ac_in_power_w.unwrap() - 0.0).abs() < f32::EPSILON);
+        assert!((result.ac_out_power_w.unwrap() - 1630.0).abs() < f32::EPSILON);
+        assert!((result.pv_power_w.unwrap() - 1180.0).abs() < f32::EPSILON);
+        assert!((result.yield_today_kwh.unwrap() - 4.12).abs() < 0.001);
+    }
+
+    // Raw: [0x03, 0x00, 0x38, 0x01, 0xB4, 0x14, 0xF2, 0x08, 0xAE, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    // Bulk charging from the grid on AC in 1, no PV data available
+    #[test]
+    fn test_multi_rs_state_parse_2() {
+        let test_data = [
+            0x03, 0x00, 0x38, 0x01, 0xB4, 0x14, 0xF2, 0x08, 0xAE, 0x01, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF,
+        ];
+
+        let result = MultiRsState::parse(&test_data).unwrap();
+
+        assert_eq!(result.mode, Mode::Bulk);
+        assert!((result.battery_current_a.unwrap() - 31.2).abs() < 0.001);
+        assert!((result.battery_voltage_v.unwrap() - 53.00).abs() < 0.001);
+        assert_eq!(result.ac_in_state, AcInState::AcIn1);
+        assert!((result.ac_in_power_w.unwrap() - 2290.0).abs() < f32::EPSILON);
+        assert!((result.ac_out_power_w.unwrap() - 430.0).abs() < f32::EPSILON);
+        assert!(result.pv_power_w.is_none());
+        assert!(result.yield_today_kwh.is_none());
+    }
+}
@@ -0,0 +1,17 @@
+use bitflags::bitflags;
+
+bitflags! {
+    #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct OffReason: u32 {
+        const NoInputPower          = 0x001;
+        const SwitchedOffSwitch     = 0x002;
+        const SwitchedOffRegister   = 0x004;
+        const RemoteInput           = 0x008;
+        const ProtectionActive      = 0x010;
+        const PayGo                 = 0x020;
+        const Bms                   = 0x040;
+        const EngineShutdown        = 0x080;
+        const AnalysingInputVoltage = 0x100;
+    }
+}
@@ -0,0 +1,133 @@
+use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
+use crate::err::*;
+
+use super::error_state::ErrorState;
+use super::mode::Mode;
+use super::off_reason::OffReason;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct OrionXsState {
+    pub mode: Mode,
+    pub error_state: ErrorState,
+    pub output_voltage_v: Option<f32>,
+    pub output_current_a: Option<f32>,
+    pub input_voltage_v: Option<f32>,
+    pub input_current_a: Option<f32>,
+    pub off_reason: OffReason,
+}
+
+impl OrionXsState {
+    pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(payload);
+
+        let mode = Mode::from(reader.read_unsigned_int(8)? as u8);
+        let error_state = ErrorState::from(reader.read_unsigned_int(8)? as u8);
+        let output_voltage_v = reader.read_signed_field(16, 0x7FFF, 0.01)?;
+        let output_current_a = reader.read_signed_field(16, 0x7FFF, 0.1)?;
+        let input_voltage_v = reader.read_unsigned_field(16, 0xFFFF, 0.01, 0.0)?;
+        let input_current_a = reader.read_unsigned_field(16, 0xFFFF, 0.1, 0.0)?;
+        let off_reason = OffReason::from_bits_retain(reader.read_unsigned_int(32)? as u32);
+
+        Ok(Self {
+            mode,
+            error_state,
+            output_voltage_v,
+            output_current_a,
+            input_voltage_v,
+            input_current_a,
+            off_reason,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(8, u8::from(self.mode) as u64)?;
+        writer.write_unsigned_int(8, u8::from(self.error_state) as u64)?;
+        writer.write_signed_field(16, 0x7FFF, 0.01, self.output_voltage_v)?;
+        writer.write_signed_field(16, 0x7FFF, 0.1, self.output_current_a)?;
+        writer.write_unsigned_field(16, 0xFFFF, 0.01, 0.0, self.input_voltage_v)?;
+        writer.write_unsigned_field(16, 0xFFFF, 0.1, 0.0, self.input_current_a)?;
+        writer.write_unsigned_int(32, self.off_reason.bits() as u64)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        matches!(self.mode, Mode::Unknown(_))
+            || matches!(self.error_state, ErrorState::Unknown(_))
+            || OffReason::from_bits(self.off_reason.bits()).is_none()
+    }
+
+    pub fn input_power_w(&self) -> Option<f32> {
+        Some(self.input_voltage_v? * self.input_current_a?)
+    }
+
+    pub fn output_power_w(&self) -> Option<f32> {
+        Some(self.output_voltage_v? * self.output_current_a?)
+    }
+
+    /// Output power as a fraction of input power. `None` if either is
+    /// unavailable or no power is being drawn from the input.
+    pub fn efficiency(&self) -> Option<f32> {
+        let input_power_w = self.input_power_w()?;
+        if input_power_w <= 0.0 {
+            return None;
+        }
+        Some(self.output_power_w()? / input_power_w)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Raw: [0x03, 0x00, 0x8C, 0x05, 0x2C, 0x01, 0x46, 0x05, 0x54, 0x01, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF]
+    // Bulk charging the house bank from the alternator
+    #[test]
+    fn test_orion_xs_state_parse_1() {
+        let test_data = [
+            0x03, 0x00, 0x8C, 0x05, 0x2C, 0x01, 0x46, 0x05, 0x54, 0x01, 0x00, 0x00, 0x00, 0x00,
+            0xFF, 0xFF,
+        ];
+
+        let result = OrionXsState::parse(&test_data).unwrap();
+
+        assert_eq!(result.mode, Mode::Bulk);
+        assert_eq!(result.error_state, ErrorState::NoError);
+        assert!((result.output_voltage_v.unwrap() - 14.20).abs() < 0.001);
+        assert!((result.output_current_a.unwrap() - 30.0).abs() < 0.001);
+        assert!((result.input_voltage_v.unwrap() - 13.50).abs() < 0.001);
+        assert!((result.input_current_a.unwrap() - 34.0).abs() < 0.001);
+        assert!(result.off_reason.is_empty());
+        assert!((result.output_power_w().unwrap() - 426.0).abs() < 0.01);
+        assert!((result.input_power_w().unwrap() - 459.0).abs() < 0.01);
+        assert!((result.efficiency().unwrap() - 426.0 / 459.0).abs() < 0.001);
+    }
+
+    // Raw: [0x00, 0x00, 0xE2, 0x04, 0x00, 0x00, 0xC4, 0x04, 0x00, 0x00, 0x81, 0x00, 0x00, 0x00, 0xFF, 0xFF]
+    // Switched off by engine shutdown detection
+    #[test]
+    fn test_orion_xs_state_parse_2() {
+        let test_data = [
+            0x00, 0x00, 0xE2, 0x04, 0x00, 0x00, 0xC4, 0x04, 0x00, 0x00, 0x81, 0x00, 0x00, 0x00,
+            0xFF, 0xFF,
+        ];
+
+        let result = OrionXsState::parse(&test_data).unwrap();
+
+        assert_eq!(result.mode, Mode::Off);
+        assert!((result.output_voltage_v.unwrap() - 12.50).abs() < 0.001);
+        assert!((result.output_current_a.unwrap() - 0.0).abs() < f32::EPSILON);
+        assert!((result.input_voltage_v.unwrap() - 12.20).abs() < 0.001);
+        assert!((result.input_current_a.unwrap() - 0.0).abs() < f32::EPSILON);
+        assert_eq!(
+            result.off_reason,
+            OffReason::NoInputPower | OffReason::EngineShutdown
+        );
+        assert!(result.efficiency().is_none());
+    }
+}
@@ -0,0 +1,469 @@
+use strum::Display;
+
+/// A Victron product, as identified by the product ID in an advertisement header.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Product {
+    pub id: u16,
+    pub name: &'static str,
+    pub family: ProductFamily,
+    pub ratings: ProductRatings,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Display)]
+pub enum ProductFamily {
+    /// BMV and SmartShunt battery monitors.
+    BatteryMonitor,
+    /// BlueSolar and SmartSolar MPPT solar chargers.
+    SolarCharger,
+    /// Phoenix inverters.
+    Inverter,
+    /// Phoenix Smart and Blue Smart AC chargers.
+    AcCharger,
+    /// Orion Smart and Orion XS DC/DC converters.
+    DcDcConverter,
+    /// Smart Lithium batteries.
+    SmartLithium,
+    /// Inverter RS and Inverter RS Smart Solar inverters.
+    InverterRs,
+    /// Cerbo GX and other GX devices.
+    GxDevice,
+    /// Smart BatteryProtect load disconnects.
+    SmartBatteryProtect,
+    /// Lynx Smart BMS battery management systems.
+    LynxSmartBms,
+    /// Multi RS Solar inverter chargers.
+    MultiRs,
+}
+
+/// The rated limits of a product, as far as they are known. Which fields are
+/// set depends on the product family.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ProductRatings {
+    /// Nominal battery voltage.
+    pub battery_voltage_v: Option<u16>,
+    /// Maximum charge current for chargers, maximum measured current for battery monitors.
+    pub max_current_a: Option<u16>,
+    /// Maximum PV open circuit voltage.
+    pub max_pv_voltage_v: Option<u16>,
+    /// Continuous output power.
+    pub output_power_va: Option<u16>,
+    /// Nominal AC output voltage.
+    pub ac_voltage_v: Option<u16>,
+}
+
+impl Product {
+    /// Look up a product by its product ID.
+    pub fn from_id(product_id: u16) -> Option<&'static Product> {
+        PRODUCTS
+            .binary_search_by_key(&product_id, |p| p.id)
+            .ok()
+            .map(|i| &PRODUCTS[i])
+    }
+
+    /// All known products, ordered by product ID.
+    pub fn all() -> &'static [Product] {
+        PRODUCTS
+    }
+
+    const fn battery_monitor(id: u16, name: &'static str, max_current_a: Option<u16>) -> Self {
+        Self {
+            id,
+            name,
+            family: ProductFamily::BatteryMonitor,
+            ratings: ProductRatings {
+                battery_voltage_v: None,
+                max_current_a,
+                max_pv_voltage_v: None,
+                output_power_va: None,
+                ac_voltage_v: None,
+            },
+        }
+    }
+
+    const fn solar_charger(
+        id: u16,
+        name: &'static str,
+        max_pv_voltage_v: u16,
+        max_current_a: u16,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            family: ProductFamily::SolarCharger,
+            ratings: ProductRatings {
+                battery_voltage_v: None,
+                max_current_a: Some(max_current_a),
+                max_pv_voltage_v: Some(max_pv_voltage_v),
+                output_power_va: None,
+                ac_voltage_v: None,
+            },
+        }
+    }
+
+    const fn inverter(
+        id: u16,
+        name: &'static str,
+        battery_voltage_v: u16,
+        output_power_va: u16,
+        ac_voltage_v: u16,
+    ) -> Self {
+        Self::inverter_of_family(
+            ProductFamily::Inverter,
+            id,
+            name,
+            battery_voltage_v,
+            output_power_va,
+            ac_voltage_v,
+        )
+    }
+
+    const fn inverter_of_family(
+        family: ProductFamily,
+        id: u16,
+        name: &'static str,
+        battery_voltage_v: u16,
+        output_power_va: u16,
+        ac_voltage_v: u16,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            family,
+            ratings: ProductRatings {
+                battery_voltage_v: Some(battery_voltage_v),
+                max_current_a: None,
+                max_pv_voltage_v: None,
+                output_power_va: Some(output_power_va),
+                ac_voltage_v: Some(ac_voltage_v),
+            },
+        }
+    }
+
+    const fn ac_charger(
+        id: u16,
+        name: &'static str,
+        battery_voltage_v: u16,
+        max_current_a: u16,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            family: ProductFamily::AcCharger,
+            ratings: ProductRatings {
+                battery_voltage_v: Some(battery_voltage_v),
+                max_current_a: Some(max_current_a),
+                max_pv_voltage_v: None,
+                output_power_va: None,
+                ac_voltage_v: None,
+            },
+        }
+    }
+
+    const fn dc_dc_converter(
+        id: u16,
+        name: &'static str,
+        battery_voltage_v: Option<u16>,
+        max_current_a: Option<u16>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            family: ProductFamily::DcDcConverter,
+            ratings: ProductRatings {
+                battery_voltage_v,
+                max_current_a,
+                max_pv_voltage_v: None,
+                output_power_va: None,
+                ac_voltage_v: None,
+            },
+        }
+    }
+
+    const fn smart_lithium(id: u16, name: &'static str, battery_voltage_v: u16) -> Self {
+        Self::unrated(id, name, ProductFamily::SmartLithium).with_battery_voltage(battery_voltage_v)
+    }
+
+    const fn smart_battery_protect(id: u16, name: &'static str, max_current_a: u16) -> Self {
+        Self::unrated(id, name, ProductFamily::SmartBatteryProtect).with_max_current(max_current_a)
+    }
+
+    const fn lynx_smart_bms(id: u16, name: &'static str, max_current_a: u16) -> Self {
+        Self::unrated(id, name, ProductFamily::LynxSmartBms).with_max_current(max_current_a)
+    }
+
+    const fn gx_device(id: u16, name: &'static str) -> Self {
+        Self::unrated(id, name, ProductFamily::GxDevice)
+    }
+
+    const fn unrated(id: u16, name: &'static str, family: ProductFamily) -> Self {
+        Self {
+            id,
+            name,
+            family,
+            ratings: ProductRatings {
+                battery_voltage_v: None,
+                max_current_a: None,
+                max_pv_voltage_v: None,
+                output_power_va: None,
+                ac_voltage_v: None,
+            },
+        }
+    }
+
+    const fn with_battery_voltage(mut self, battery_voltage_v: u16) -> Self {
+        self.ratings.battery_voltage_v = Some(battery_voltage_v);
+        self
+    }
+
+    const fn with_max_current(mut self, max_current_a: u16) -> Self {
+        self.ratings.max_current_a = Some(max_current_a);
+        self
+    }
+}
+
+/// Known products, ordered by product ID.
+///
+/// The VE.Direct products are taken from the product ID table of the VE.Direct protocol
+/// document in `docs/`. Products without a VE.Direct port, such as the GX devices, Smart
+/// Lithium batteries and the RS series, are not in that table and are listed with the product
+/// ID they publish in their advertisements.
+const PRODUCTS: &[Product] = &[
+    Product::battery_monitor(0x0203, "BMV-700", None),
+    Product::battery_monitor(0x0204, "BMV-702", None),
+    Product::battery_monitor(0x0205, "BMV-700H", None),
+    Product::solar_charger(0x0300, "BlueSolar MPPT 70/15", 70, 15),
+    Product::solar_charger(0xA040, "BlueSolar MPPT 75/50", 75, 50),
+    Product::solar_charger(0xA041, "BlueSolar MPPT 150/35", 150, 35),
+    Product::solar_charger(0xA042, "BlueSolar MPPT 75/15", 75, 15),
+    Product::solar_charger(0xA043, "BlueSolar MPPT 100/15", 100, 15),
+    Product::solar_charger(0xA044, "BlueSolar MPPT 100/30", 100, 30),
+    Product::solar_charger(0xA045, "BlueSolar MPPT 100/50", 100, 50),
+    Product::solar_charger(0xA046, "BlueSolar MPPT 150/70", 150, 70),
+    Product::solar_charger(0xA047, "BlueSolar MPPT 150/100", 150, 100),
+    Product::solar_charger(0xA049, "BlueSolar MPPT 100/50 rev2", 100, 50),
+    Product::solar_charger(0xA04A, "BlueSolar MPPT 100/30 rev2", 100, 30),
+    Product::solar_charger(0xA04B, "BlueSolar MPPT 150/35 rev2", 150, 35),
+    Product::solar_charger(0xA04C, "BlueSolar MPPT 75/10", 75, 10),
+    Product::solar_charger(0xA04D, "BlueSolar MPPT 150/45", 150, 45),
+    Product::solar_charger(0xA04E, "BlueSolar MPPT 150/60", 150, 60),
+    Product::solar_charger(0xA04F, "BlueSolar MPPT 150/85", 150, 85),
+    Product::solar_charger(0xA050, "SmartSolar MPPT 250/100", 250, 100),
+    Product::solar_charger(0xA051, "SmartSolar MPPT 150/100", 150, 100),
+    Product::solar_charger(0xA052, "SmartSolar MPPT 150/85", 150, 85),
+    Product::solar_charger(0xA053, "SmartSolar MPPT 75/15", 75, 15),
+    Product::solar_charger(0xA054, "SmartSolar MPPT 75/10", 75, 10),
+    Product::solar_charger(0xA055, "SmartSolar MPPT 100/15", 100, 15),
+    Product::solar_charger(0xA056, "SmartSolar MPPT 100/30", 100, 30),
+    Product::solar_charger(0xA057, "SmartSolar MPPT 100/50", 100, 50),
+    Product::solar_charger(0xA058, "SmartSolar MPPT 150/35", 150, 35),
+    Product::solar_charger(0xA059, "SmartSolar MPPT 150/100 rev2", 150, 100),
+    Product::solar_charger(0xA05A, "SmartSolar MPPT 150/85 rev2", 150, 85),
+    Product::solar_charger(0xA05B, "SmartSolar MPPT 250/70", 250, 70),
+    Product::solar_charger(0xA05C, "SmartSolar MPPT 250/85", 250, 85),
+    Product::solar_charger(0xA05D, "SmartSolar MPPT 250/60", 250, 60),
+    Product::solar_charger(0xA05E, "SmartSolar MPPT 250/45", 250, 45),
+    Product::solar_charger(0xA05F, "SmartSolar MPPT 100/20", 100, 20),
+    Product::solar_charger(0xA060, "SmartSolar MPPT 100/20 48V", 100, 20),
+    Product::solar_charger(0xA061, "SmartSolar MPPT 150/45", 150, 45),
+    Product::solar_charger(0xA062, "SmartSolar MPPT 150/60", 150, 60),
+    Product::solar_charger(0xA063, "SmartSolar MPPT 150/70", 150, 70),
+    Product::solar_charger(0xA064, "SmartSolar MPPT 250/85 rev2", 250, 85),
+    Product::solar_charger(0xA065, "SmartSolar MPPT 250/100 rev2", 250, 100),
+    Product::solar_charger(0xA066, "BlueSolar MPPT 100/20", 100, 20),
+    Product::solar_charger(0xA067, "BlueSolar MPPT 100/20 48V", 100, 20),
+    Product::solar_charger(0xA068, "SmartSolar MPPT 250/60 rev2", 250, 60),
+    Product::solar_charger(0xA069, "SmartSolar MPPT 250/70 rev2", 250, 70),
+    Product::solar_charger(0xA06A, "SmartSolar MPPT 150/45 rev2", 150, 45),
+    Product::solar_charger(0xA06B, "SmartSolar MPPT 150/60 rev2", 150, 60),
+    Product::solar_charger(0xA06C, "SmartSolar MPPT 150/70 rev2", 150, 70),
+    Product::solar_charger(0xA06D, "SmartSolar MPPT 150/85 rev3", 150, 85),
+    Product::solar_charger(0xA06E, "SmartSolar MPPT 150/100 rev3", 150, 100),
+    Product::solar_charger(0xA06F, "BlueSolar MPPT 150/45 rev2", 150, 45),
+    Product::solar_charger(0xA070, "BlueSolar MPPT 150/60 rev2", 150, 60),
+    Product::solar_charger(0xA071, "BlueSolar MPPT 150/70 rev2", 150, 70),
+    Product::solar_charger(0xA072, "BlueSolar MPPT 150/45 rev3", 150, 45),
+    Product::solar_charger(0xA073, "SmartSolar MPPT 150/45 rev3", 150, 45),
+    Product::solar_charger(0xA074, "SmartSolar MPPT 75/10 rev2", 75, 10),
+    Product::solar_charger(0xA075, "SmartSolar MPPT 75/15 rev2", 75, 15),
+    Product::solar_charger(0xA076, "BlueSolar MPPT 100/30 rev3", 100, 30),
+    Product::solar_charger(0xA077, "BlueSolar MPPT 100/50 rev3", 100, 50),
+    Product::solar_charger(0xA078, "BlueSolar MPPT 150/35 rev3", 150, 35),
+    Product::solar_charger(0xA079, "BlueSolar MPPT 75/10 rev2", 75, 10),
+    Product::solar_charger(0xA07A, "BlueSolar MPPT 75/15 rev2", 75, 15),
+    Product::solar_charger(0xA07B, "BlueSolar MPPT 100/15 rev2", 100, 15),
+    Product::solar_charger(0xA07C, "BlueSolar MPPT 75/10 rev3", 75, 10),
+    Product::solar_charger(0xA07D, "BlueSolar MPPT 75/15 rev3", 75, 15),
+    Product::solar_charger(0xA07E, "SmartSolar MPPT 100/30 12V", 100, 30),
+    Product::solar_charger(0xA07F, "All-In-1 SmartSolar MPPT 75/15 12V", 75, 15),
+    Product::solar_charger(0xA080, "SmartSolar MPPT 250/60 rev3", 250, 60),
+    Product::solar_charger(0xA081, "SmartSolar MPPT 250/70 rev3", 250, 70),
+    Product::solar_charger(0xA102, "SmartSolar MPPT VE.Can 150/70", 150, 70),
+    Product::solar_charger(0xA103, "SmartSolar MPPT VE.Can 150/45", 150, 45),
+    Product::solar_charger(0xA104, "SmartSolar MPPT VE.Can 150/60", 150, 60),
+    Product::solar_charger(0xA105, "SmartSolar MPPT VE.Can 150/85", 150, 85),
+    Product::solar_charger(0xA106, "SmartSolar MPPT VE.Can 150/100", 150, 100),
+    Product::solar_charger(0xA107, "SmartSolar MPPT VE.Can 250/45", 250, 45),
+    Product::solar_charger(0xA108, "SmartSolar MPPT VE.Can 250/60", 250, 60),
+    Product::solar_charger(0xA109, "SmartSolar MPPT VE.Can 250/70", 250, 70),
+    Product::solar_charger(0xA10A, "SmartSolar MPPT VE.Can 250/85", 250, 85),
+    Product::solar_charger(0xA10B, "SmartSolar MPPT VE.Can 250/100", 250, 100),
+    Product::solar_charger(0xA10C, "SmartSolar MPPT VE.Can 150/70 rev2", 150, 70),
+    Product::solar_charger(0xA10D, "SmartSolar MPPT VE.Can 150/85 rev2", 150, 85),
+    Product::solar_charger(0xA10E, "SmartSolar MPPT VE.Can 150/100 rev2", 150, 100),
+    Product::solar_charger(0xA10F, "BlueSolar MPPT VE.Can 150/100", 150, 100),
+    Product::solar_charger(0xA112, "BlueSolar MPPT VE.Can 250/70", 250, 70),
+    Product::solar_charger(0xA113, "BlueSolar MPPT VE.Can 250/100", 250, 100),
+    Product::solar_charger(0xA114, "SmartSolar MPPT VE.Can 250/70 rev2", 250, 70),
+    Product::solar_charger(0xA115, "SmartSolar MPPT VE.Can 250/100 rev2", 250, 100),
+    Product::solar_charger(0xA116, "SmartSolar MPPT VE.Can 250/85 rev2", 250, 85),
+    Product::solar_charger(0xA117, "BlueSolar MPPT VE.Can 150/100 rev2", 150, 100),
+    Product::gx_device(0xA160, "Cerbo GX"),
+    Product::inverter(0xA201, "Phoenix Inverter 12V 250VA 230V", 12, 250, 230),
+    Product::inverter(0xA202, "Phoenix Inverter 24V 250VA 230V", 24, 250, 230),
+    Product::inverter(0xA204, "Phoenix Inverter 48V 250VA 230V", 48, 250, 230),
+    Product::inverter(0xA211, "Phoenix Inverter 12V 375VA 230V", 12, 375, 230),
+    Product::inverter(0xA212, "Phoenix Inverter 24V 375VA 230V", 24, 375, 230),
+    Product::inverter(0xA214, "Phoenix Inverter 48V 375VA 230V", 48, 375, 230),
+    Product::inverter(0xA221, "Phoenix Inverter 12V 500VA 230V", 12, 500, 230),
+    Product::inverter(0xA222, "Phoenix Inverter 24V 500VA 230V", 24, 500, 230),
+    Product::inverter(0xA224, "Phoenix Inverter 48V 500VA 230V", 48, 500, 230),
+    Product::inverter(0xA231, "Phoenix Inverter 12V 250VA 230V", 12, 250, 230),
+    Product::inverter(0xA232, "Phoenix Inverter 24V 250VA 230V", 24, 250, 230),
+    Product::inverter(0xA234, "Phoenix Inverter 48V 250VA 230V", 48, 250, 230),
+    Product::inverter(0xA239, "Phoenix Inverter 12V 250VA 120V", 12, 250, 120),
+    Product::inverter(0xA23A, "Phoenix Inverter 24V 250VA 120V", 24, 250, 120),
+    Product::inverter(0xA23C, "Phoenix Inverter 48V 250VA 120V", 48, 250, 120),
+    Product::inverter(0xA241, "Phoenix Inverter 12V 375VA 230V", 12, 375, 230),
+    Product::inverter(0xA242, "Phoenix Inverter 24V 375VA 230V", 24, 375, 230),
+    Product::inverter(0xA244, "Phoenix Inverter 48V 375VA 230V", 48, 375, 230),
+    Product::inverter(0xA249, "Phoenix Inverter 12V 375VA 120V", 12, 375, 120),
+    Product::inverter(0xA24A, "Phoenix Inverter 24V 375VA 120V", 24, 375, 120),
+    Product::inverter(0xA24C, "Phoenix Inverter 48V 375VA 120V", 48, 375, 120),
+    Product::inverter(0xA251, "Phoenix Inverter 12V 500VA 230V", 12, 500, 230),
+    Product::inverter(0xA252, "Phoenix Inverter 24V 500VA 230V", 24, 500, 230),
+    Product::inverter(0xA254, "Phoenix Inverter 48V 500VA 230V", 48, 500, 230),
+    Product::inverter(0xA259, "Phoenix Inverter 12V 500VA 120V", 12, 500, 120),
+    Product::inverter(0xA25A, "Phoenix Inverter 24V 500VA 120V", 24, 500, 120),
+    Product::inverter(0xA25C, "Phoenix Inverter 48V 500VA 120V", 48, 500, 120),
+    Product::inverter(0xA261, "Phoenix Inverter 12V 800VA 230V", 12, 800, 230),
+    Product::inverter(0xA262, "Phoenix Inverter 24V 800VA 230V", 24, 800, 230),
+    Product::inverter(0xA264, "Phoenix Inverter 48V 800VA 230V", 48, 800, 230),
+    Product::inverter(0xA269, "Phoenix Inverter 12V 800VA 120V", 12, 800, 120),
+    Product::inverter(0xA26A, "Phoenix Inverter 24V 800VA 120V", 24, 800, 120),
+    Product::inverter(0xA26C, "Phoenix Inverter 48V 800VA 120V", 48, 800, 120),
+    Product::inverter(0xA271, "Phoenix Inverter 12V 1200VA 230V", 12, 1200, 230),
+    Product::inverter(0xA272, "Phoenix Inverter 24V 1200VA 230V", 24, 1200, 230),
+    Product::inverter(0xA274, "Phoenix Inverter 48V 1200VA 230V", 48, 1200, 230),
+    Product::inverter(0xA279, "Phoenix Inverter 12V 1200VA 120V", 12, 1200, 120),
+    Product::inverter(0xA27A, "Phoenix Inverter 24V 1200VA 120V", 24, 1200, 120),
+    Product::inverter(0xA27C, "Phoenix Inverter 48V 1200VA 120V", 48, 1200, 120),
+    Product::inverter(0xA281, "Phoenix Inverter 12V 1600VA 230V", 12, 1600, 230),
+    Product::inverter(0xA282, "Phoenix Inverter 24V 1600VA 230V", 24, 1600, 230),
+    Product::inverter(0xA284, "Phoenix Inverter 48V 1600VA 230V", 48, 1600, 230),
+    Product::inverter(0xA291, "Phoenix Inverter 12V 2000VA 230V", 12, 2000, 230),
+    Product::inverter(0xA292, "Phoenix Inverter 24V 2000VA 230V", 24, 2000, 230),
+    Product::inverter(0xA294, "Phoenix Inverter 48V 2000VA 230V", 48, 2000, 230),
+    Product::inverter(0xA2A1, "Phoenix Inverter 12V 3000VA 230V", 12, 3000, 230),
+    Product::inverter(0xA2A2, "Phoenix Inverter 24V 3000VA 230V", 24, 3000, 230),
+    Product::inverter(0xA2A4, "Phoenix Inverter 48V 3000VA 230V", 48, 3000, 230),
+    Product::inverter_of_family(
+        ProductFamily::InverterRs,
+        0xA2FF,
+        "Inverter RS Smart 48V/6000VA/230V",
+        48,
+        6000,
+        230,
+    ),
+    Product::ac_charger(0xA330, "Blue Smart IP22 Charger 12/15 (1)", 12, 15),
+    Product::ac_charger(0xA331, "Blue Smart IP22 Charger 12/20 (1)", 12, 20),
+    Product::ac_charger(0xA332, "Blue Smart IP22 Charger 12/20 (3)", 12, 20),
+    Product::ac_charger(0xA333, "Blue Smart IP22 Charger 12/30 (1)", 12, 30),
+    Product::ac_charger(0xA334, "Blue Smart IP22 Charger 12/30 (3)", 12, 30),
+    Product::ac_charger(0xA340, "Phoenix Smart IP43 Charger 12/50 (1+1)", 12, 50),
+    Product::ac_charger(0xA341, "Phoenix Smart IP43 Charger 12/50 (3)", 12, 50),
+    Product::ac_charger(0xA342, "Phoenix Smart IP43 Charger 24/25 (1+1)", 24, 25),
+    Product::ac_charger(0xA343, "Phoenix Smart IP43 Charger 24/25 (3)", 24, 25),
+    Product::ac_charger(0xA344, "Phoenix Smart IP43 Charger 12/30 (1+1)", 12, 30),
+    Product::ac_charger(0xA345, "Phoenix Smart IP43 Charger 12/30 (3)", 12, 30),
+    Product::ac_charger(0xA346, "Phoenix Smart IP43 Charger 24/16 (1+1)", 24, 16),
+    Product::ac_charger(0xA347, "Phoenix Smart IP43 Charger 24/16 (3)", 24, 16),
+    Product::battery_monitor(0xA381, "BMV-712 Smart", None),
+    Product::battery_monitor(0xA382, "BMV-710H Smart", None),
+    Product::battery_monitor(0xA383, "BMV-712 Smart Rev2", None),
+    Product::battery_monitor(0xA389, "SmartShunt 500A/50mV", Some(500)),
+    Product::battery_monitor(0xA38A, "SmartShunt 1000A/50mV", Some(1000)),
+    Product::battery_monitor(0xA38B, "SmartShunt 2000A/50mV", Some(2000)),
+    Product::dc_dc_converter(
+        0xA3C0,
+        "Orion Smart 12V/12V-18A Isolated",
+        Some(12),
+        Some(18),
+    ),
+    Product::dc_dc_converter(
+        0xA3C1,
+        "Orion Smart 12V/24V-10A Isolated",
+        Some(24),
+        Some(10),
+    ),
+    Product::dc_dc_converter(
+        0xA3C2,
+        "Orion Smart 24V/12V-20A Isolated",
+        Some(12),
+        Some(20),
+    ),
+    Product::dc_dc_converter(
+        0xA3C3,
+        "Orion Smart 24V/24V-12A Isolated",
+        Some(24),
+        Some(12),
+    ),
+    Product::smart_battery_protect(0xA3D0, "Smart BatteryProtect 12/24V-65A", 65),
+    Product::smart_battery_protect(0xA3D1, "Smart BatteryProtect 12/24V-100A", 100),
+    Product::smart_battery_protect(0xA3D2, "Smart BatteryProtect 12/24V-220A", 220),
+    Product::smart_battery_protect(0xA3D3, "Smart BatteryProtect 48V-100A", 100),
+    Product::smart_lithium(0xA3E0, "Smart Lithium 12.8V", 12),
+    Product::smart_lithium(0xA3E1, "Smart Lithium 25.6V", 24),
+    Product::lynx_smart_bms(0xA3E5, "Lynx Smart BMS 500", 500),
+    Product::lynx_smart_bms(0xA3E6, "Lynx Smart BMS 1000", 1000),
+    Product::dc_dc_converter(0xA3F0, "Orion XS 12V/12V-50A", Some(12), Some(50)),
+    Product::dc_dc_converter(0xA3F1, "Orion XS 1400", None, None),
+    Product::inverter_of_family(
+        ProductFamily::MultiRs,
+        0xA442,
+        "Multi RS Solar 48V/6000VA/230V",
+        48,
+        6000,
+        230,
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_products_ordered_by_id() {
+        assert!(PRODUCTS.windows(2).all(|w| w[0].id < w[1].id));
+    }
+
+    #[test]
+    fn test_product_from_id() {
+        let product = Product::from_id(0xA04A).unwrap();
+
+        assert_eq!(product.name, "BlueSolar MPPT 100/30 rev2");
+        assert_eq!(product.family, ProductFamily::SolarCharger);
+        assert_eq!(product.ratings.max_pv_voltage_v, Some(100));
+        assert_eq!(product.ratings.max_current_a, Some(30));
+
+        let product = Product::from_id(0xA389).unwrap();
+
+        assert_eq!(product.name, "SmartShunt 500A/50mV");
+        assert_eq!(product.family, ProductFamily::BatteryMonitor);
+        assert_eq!(product.ratings.max_current_a, Some(500));
+
+        assert!(Product::from_id(0xFFFF).is_none());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
+use crate::err::*;
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+use super::alarm_reason::AlarmReason;
+use super::error_state::ErrorState;
+use super::mode::Mode;
+use super::off_reason::OffReason;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct SmartBatteryProtectState {
+    pub mode: Mode,
+    pub output_state: OutputState,
+    pub error_state: ErrorState,
+    pub alarm_reason: AlarmReason,
+    pub warning_reason: AlarmReason,
+    pub input_voltage_v: Option<f32>,
+    pub output_voltage_v: Option<f32>,
+    pub off_reason: OffReason,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, FromPrimitive, IntoPrimitive)]
+#[repr(u8)]
+pub enum OutputState {
+    NotApplicable = 0xFF,
+    On = 1,
+    Off = 4,
+    /// An output state that is not known to this version of the crate, holding the raw value.
+    #[num_enum(catch_all)]
+    Unknown(u8),
+}
+
+impl SmartBatteryProtectState {
+    pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(payload);
+
+        let mode = Mode::from(reader.read_unsigned_int(8)? as u8);
+        let output_state = OutputState::from(reader.read_unsigned_int(8)? as u8);
+        let error_state = ErrorState::from(reader.read_unsigned_int(8)? as u8);
+        let alarm_reason = AlarmReason::from_bits_retain(reader.read_signed_int(16)?);
+        let warning_reason = AlarmReason::from_bits_retain(reader.read_signed_int(16)?);
+        let input_voltage_v = reader.read_signed_field(16, 0x7FFF, 0.01)?;
+        let output_voltage_v = reader.read_unsigned_field(16, 0xFFFF, 0.01, 0.0)?;
+        let off_reason = OffReason::from_bits_retain(reader.read_unsigned_int(32)? as u32);
+
+        Ok(Self {
+            mode,
+            output_state,
+            error_state,
+            alarm_reason,
+            warning_reason,
+            input_voltage_v,
+            output_voltage_v,
+            off_reason,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(8, u8::from(self.mode) as u64)?;
+        writer.write_unsigned_int(8, u8::from(self.output_state) as u64)?;
+        writer.write_unsigned_int(8, u8::from(self.error_state) as u64)?;
+        writer.write_signed_int(16, self.alarm_reason.bits())?;
+        writer.write_signed_int(16, self.warning_reason.bits())?;
+        writer.write_signed_field(16, 0x7FFF, 0.01, self.input_voltage_v)?;
+        writer.write_unsigned_field(16, 0xFFFF, 0.01, 0.0, self.output_voltage_v)?;
+        writer.write_unsigned_int(32, self.off_reason.bits() as u64)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        matches!(self.mode, Mode::Unknown(_))
+            || matches!(self.output_state, OutputState::Unknown(_))
+            || matches!(self.error_state, ErrorState::Unknown(_))
+            || AlarmReason::from_bits(self.alarm_reason.bits()).is_none()
+            || AlarmReason::from_bits(self.warning_reason.bits()).is_none()
+            || OffReason::from_bits(self.off_reason.bits()).is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Raw: [0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x28, 0x05, 0x24, 0x05, 0x00, 0x00, 0x00, 0x00, 0xFF]
+    // Load connected
+    #[test]
+    fn test_smart_battery_protect_state_parse_1() {
+        let test_data = [
+            0x09, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x28, 0x05, 0x24, 0x05, 0x00, 0x00, 0x00,
+            0x00, 0xFF,
+        ];
+
+        let result = SmartBatteryProtectState::parse(&test_data).unwrap();
+
+        assert_eq!(result.output_state, OutputState::On);
+        assert_eq!(result.error_state, ErrorState::NoError);
+        assert!(result.alarm_reason.is_empty());
+        assert!(result.warning_reason.is_empty());
+        assert!((result.input_voltage_v.unwrap() - 13.20).abs() < f32::EPSILON);
+        assert!((result.output_voltage_v.unwrap() - 13.16).abs() < 0.001);
+        assert!(result.off_reason.is_empty());
+    }
+
+    // Raw: [0x00, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x74, 0x04, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00, 0xFF]
+    // Load disconnected because of low battery voltage
+    #[test]
+    fn test_smart_battery_protect_state_parse_2() {
+        let test_data = [
+            0x00, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x74, 0x04, 0x00, 0x00, 0x10, 0x00, 0x00,
+            0x00, 0xFF,
+        ];
+
+        let result = SmartBatteryProtectState::parse(&test_data).unwrap();
+
+        assert_eq!(result.mode, Mode::Off);
+        assert_eq!(result.output_state, OutputState::Off);
+        assert_eq!(result.error_state, ErrorState::NoError);
+        assert_eq!(result.alarm_reason, AlarmReason::LowVoltage);
+        assert!(result.warning_reason.is_empty());
+        assert!((result.input_voltage_v.unwrap() - 11.40).abs() < 0.001);
+        assert!((result.output_voltage_v.unwrap() - 0.0).abs() < f32::EPSILON);
+        assert_eq!(result.off_reason, OffReason::ProtectionActive);
+    }
+}
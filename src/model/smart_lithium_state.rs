@@ -0,0 +1,170 @@
+use crate::bit_reader::BitReader;
+use crate::bit_writer::{unscale_unsigned, BitWriter};
+use crate::err::*;
+use num_enum::{FromPrimitive, IntoPrimitive};
+
+pub const SMART_LITHIUM_CELL_COUNT: usize = 8;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct SmartLithiumState {
+    pub bms_flags: u32,
+    pub error_flags: u16,
+    pub cell_voltages: [CellVoltage; SMART_LITHIUM_CELL_COUNT],
+    pub battery_voltage_v: Option<f32>,
+    pub balancer_status: BalancerStatus,
+    pub battery_temperature_c: Option<f32>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum CellVoltage {
+    /// The cell voltage is below 2.61V.
+    BelowRange,
+    VoltageV(f32),
+    /// The cell voltage is above 3.85V.
+    AboveRange,
+    NotApplicable,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, FromPrimitive, IntoPrimitive)]
+#[repr(u8)]
+pub enum BalancerStatus {
+    Unknown = 0,
+    Balanced = 1,
+    Balancing = 2,
+    CellImbalance = 3,
+    NotApplicable = 0xF,
+    /// A status that is not known to this version of the crate, holding the raw value.
+    /// Distinct from `Unknown`, which the device reports when it does not know the status.
+    #[num_enum(catch_all)]
+    Other(u8),
+}
+
+impl SmartLithiumState {
+    pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
+        let mut reader = BitReader::new(payload);
+
+        let bms_flags = reader.read_unsigned_int(32)? as u32;
+        let error_flags = reader.read_unsigned_int(16)? as u16;
+        let mut cell_voltages = [CellVoltage::NotApplicable; SMART_LITHIUM_CELL_COUNT];
+        for cell_voltage in cell_voltages.iter_mut() {
+            *cell_voltage = CellVoltage::parse(&mut reader)?;
+        }
+        let battery_voltage_v = reader.read_unsigned_field(12, 0xFFF, 0.01, 0.0)?;
+        let balancer_status = BalancerStatus::from(reader.read_unsigned_int(4)? as u8);
+        let battery_temperature_c = reader.read_unsigned_field(7, 0x7F, 1.0, -40.0)?;
+
+        Ok(Self {
+            bms_flags,
+            error_flags,
+            cell_voltages,
+            battery_voltage_v,
+            balancer_status,
+            battery_temperature_c,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(32, self.bms_flags as u64)?;
+        writer.write_unsigned_int(16, self.error_flags as u64)?;
+        for cell_voltage in self.cell_voltages.iter() {
+            cell_voltage.encode(&mut writer)?;
+        }
+        writer.write_unsigned_field(12, 0xFFF, 0.01, 0.0, self.battery_voltage_v)?;
+        writer.write_unsigned_int(4, u8::from(self.balancer_status) as u64)?;
+        writer.write_unsigned_field(7, 0x7F, 1.0, -40.0, self.battery_temperature_c)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        matches!(self.balancer_status, BalancerStatus::Other(_))
+    }
+}
+
+impl CellVoltage {
+    fn parse(reader: &mut BitReader) -> Result<Self> {
+        let cell_voltage = match reader.read_unsigned_int(7)? {
+            0x00 => CellVoltage::BelowRange,
+            0x7E => CellVoltage::AboveRange,
+            0x7F => CellVoltage::NotApplicable,
+            v => CellVoltage::VoltageV(v as f32 * 0.01 + 2.60),
+        };
+        Ok(cell_voltage)
+    }
+
+    fn encode(&self, writer: &mut BitWriter) -> Result<()> {
+        let int_val = match self {
+            CellVoltage::BelowRange => 0x00,
+            CellVoltage::AboveRange => 0x7E,
+            CellVoltage::NotApplicable => 0x7F,
+            CellVoltage::VoltageV(v) => match unscale_unsigned(*v, 0.01, 2.60)? {
+                int_val @ 0x01..=0x7D => int_val,
+                _ => return Err(Error::ValueOutOfRange),
+            },
+        };
+        writer.write_unsigned_int(7, int_val)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_cell_voltage(cell_voltage: CellVoltage, expected_v: f32) {
+        match cell_voltage {
+            CellVoltage::VoltageV(v) => assert!((v - expected_v).abs() < 0.001),
+            other => panic!("Expected a cell voltage, got {other:?}"),
+        }
+    }
+
+    // Raw: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC8, 0x24, 0x52, 0xF9, 0xFF, 0xFF, 0xFF, 0x38, 0x15, 0xBE]
+    // Four cell battery, balanced, cells 5-8 not present
+    #[test]
+    fn test_smart_lithium_state_parse_1() {
+        let test_data = [
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC8, 0x24, 0x52, 0xF9, 0xFF, 0xFF, 0xFF, 0x38,
+            0x15, 0xBE,
+        ];
+
+        let result = SmartLithiumState::parse(&test_data).unwrap();
+
+        assert_eq!(result.bms_flags, 0);
+        assert_eq!(result.error_flags, 0);
+        assert_cell_voltage(result.cell_voltages[0], 3.32);
+        assert_cell_voltage(result.cell_voltages[1], 3.33);
+        assert_cell_voltage(result.cell_voltages[2], 3.32);
+        assert_cell_voltage(result.cell_voltages[3], 3.34);
+        assert!(result.cell_voltages[4..]
+            .iter()
+            .all(|c| *c == CellVoltage::NotApplicable));
+        assert!((result.battery_voltage_v.unwrap() - 13.36).abs() < f32::EPSILON);
+        assert_eq!(result.balancer_status, BalancerStatus::Balanced);
+        assert!((result.battery_temperature_c.unwrap() - 22.0).abs() < f32::EPSILON);
+    }
+
+    // Raw: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0xA4, 0x5F, 0xF9, 0xFF, 0xFF, 0xFF, 0xFF, 0x2F, 0xFF]
+    // Four cell battery balancing with one cell under and one cell over range
+    #[test]
+    fn test_smart_lithium_state_parse_2() {
+        let test_data = [
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0xA4, 0x5F, 0xF9, 0xFF, 0xFF, 0xFF, 0xFF,
+            0x2F, 0xFF,
+        ];
+
+        let result = SmartLithiumState::parse(&test_data).unwrap();
+
+        assert_eq!(result.cell_voltages[0], CellVoltage::BelowRange);
+        assert_cell_voltage(result.cell_voltages[1], 3.33);
+        assert_eq!(result.cell_voltages[2], CellVoltage::AboveRange);
+        assert_cell_voltage(result.cell_voltages[3], 3.34);
+        assert!(result.battery_voltage_v.is_none());
+        assert_eq!(result.balancer_status, BalancerStatus::Balancing);
+        assert!(result.battery_temperature_c.is_none());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
 use crate::err::*;
 
 use super::error_state::ErrorState;
@@ -20,8 +21,8 @@ impl SolarChargerState {
     pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
         let mut reader = BitReader::new(payload);
 
-        let mode = Mode::try_from(reader.read_unsigned_int(8)?)?;
-        let error_state = ErrorState::try_from(reader.read_unsigned_int(8)?)?;
+        let mode = Mode::from(reader.read_unsigned_int(8)? as u8);
+        let error_state = ErrorState::from(reader.read_unsigned_int(8)? as u8);
         let battery_voltage_v = reader.read_signed_field(16, 0x7FFF, 0.01)?;
         let battery_current_a = reader.read_signed_field(16, 0x7FFF, 0.1)?;
         let yield_today_kwh = reader.read_unsigned_field(16, 0xFFFF, 0.01, 0.0)?;
@@ -38,4 +39,23 @@ impl SolarChargerState {
             load_current_a,
         })
     }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(8, u8::from(self.mode) as u64)?;
+        writer.write_unsigned_int(8, u8::from(self.error_state) as u64)?;
+        writer.write_signed_field(16, 0x7FFF, 0.01, self.battery_voltage_v)?;
+        writer.write_signed_field(16, 0x7FFF, 0.1, self.battery_current_a)?;
+        writer.write_unsigned_field(16, 0xFFFF, 0.01, 0.0, self.yield_today_kwh)?;
+        writer.write_unsigned_field(16, 0xFFFF, 1.0, 0.0, self.pv_power_w)?;
+        writer.write_unsigned_field(9, 0x1FF, 0.1, 0.0, self.load_current_a)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        matches!(self.mode, Mode::Unknown(_)) || matches!(self.error_state, ErrorState::Unknown(_))
+    }
 }
@@ -1,4 +1,5 @@
 use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
 use crate::err::*;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -19,4 +20,12 @@ impl TestRecordState {
             temperature_c,
         })
     }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+        writer.write_unsigned_field(30, 0x3FFFFFFF, 1.0, 0.0, self.uptime_s)?;
+        writer.write_unsigned_field(7, 0xFF, 1.0, -40.0, self.temperature_c)?;
+
+        Ok(writer.finish())
+    }
 }
@@ -1,6 +1,7 @@
 use super::error_state::ErrorState;
 use super::mode::Mode;
 use crate::bit_reader::BitReader;
+use crate::bit_writer::BitWriter;
 use crate::err::*;
 use num_enum::TryFromPrimitive;
 
@@ -43,8 +44,8 @@ impl VeBusState {
     pub(crate) fn parse(payload: &[u8]) -> Result<Self> {
         let mut reader = BitReader::new(payload);
 
-        let mode = Mode::try_from(reader.read_unsigned_int(8)?)?;
-        let error = ErrorState::try_from(reader.read_unsigned_int(8)?)?;
+        let mode = Mode::from(reader.read_unsigned_int(8)? as u8);
+        let error = ErrorState::from(reader.read_unsigned_int(8)? as u8);
         let battery_current_a = reader.read_signed_field(16, 0x7FFF, 0.1)?;
         let battery_voltage_v = reader.read_unsigned_field(14, 0x3FFF, 0.01, 0.0)?;
         let ac_in_state = AcInState::try_from(reader.read_unsigned_int(2)? as u8)
@@ -71,6 +72,28 @@ impl VeBusState {
             soc_percent,
         })
     }
+
+    pub(crate) fn encode(&self) -> Result<[u8; 16]> {
+        let mut writer = BitWriter::new();
+
+        writer.write_unsigned_int(8, u8::from(self.mode) as u64)?;
+        writer.write_unsigned_int(8, u8::from(self.error) as u64)?;
+        writer.write_signed_field(16, 0x7FFF, 0.1, self.battery_current_a)?;
+        writer.write_unsigned_field(14, 0x3FFF, 0.01, 0.0, self.battery_voltage_v)?;
+        writer.write_unsigned_int(2, self.ac_in_state as u64)?;
+        writer.write_signed_field(19, 0x3FFFF, 1.0, self.ac_in_power_w)?;
+        writer.write_signed_field(19, 0x3FFFF, 1.0, self.ac_out_power_w)?;
+        writer.write_unsigned_int(2, self.alarm as u64)?;
+        writer.write_unsigned_field(7, 0x7F, 1.0, -40.0, self.battery_temperature_c)?;
+        writer.write_unsigned_field(7, 0x7F, 1.0, 0.0, self.soc_percent)?;
+
+        Ok(writer.finish())
+    }
+
+    /// Whether any field holds a value that is not known to this version of the crate.
+    pub fn has_unknown_values(&self) -> bool {
+        matches!(self.mode, Mode::Unknown(_)) || matches!(self.error, ErrorState::Unknown(_))
+    }
 }
 
 #[cfg(test)]
@@ -98,7 +121,7 @@ mod test {
         assert!((result.battery_voltage_v.unwrap() - 13.50).abs() < f32::EPSILON);
         assert!((result.battery_current_a.unwrap() - 2.2).abs() < 0.1);
         assert_eq!(result.ac_in_state, AcInState::AcIn1);
-        assert!((result.ac_in_power_w.unwrap() - 47.0) < f32::EPSILON);
+        assert!((result.ac_in_power_w.unwrap() - 47.0).abs() < f32::EPSILON);
         assert!((result.battery_temperature_c.unwrap() - 26.0).abs() < f32::EPSILON);
         assert_eq!(result.alarm, AlarmNotification::NoAlarm);
         assert!(result.soc_percent.is_none());
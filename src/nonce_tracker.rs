@@ -0,0 +1,265 @@
+/// The default number of devices a `NonceTracker` can follow.
+pub const DEFAULT_NONCE_TRACKER_CAPACITY: usize = 32;
+
+/// The default number of steps a nonce may go backwards and be reported as a possible replay.
+pub const DEFAULT_REPLAY_WINDOW: u16 = 1024;
+
+/// The default number of advancing nonces behind the last one after which a device is taken
+/// to have restarted.
+pub const DEFAULT_RESTART_CONFIRMATIONS: u8 = 3;
+
+/// Tracks the last advertisement nonce of each device, used to drop repeated advertisements
+/// and to spot replayed ones.
+///
+/// A device repeats the same advertisement until its data changes, then increments the
+/// nonce, which wraps from `0xFFFF` to `0`. The nonce is published unencrypted, so
+/// advertisements can be checked with `NonceTracker::observe` before they are decrypted.
+///
+/// A nonce that goes backwards by at most the replay window is reported as a possible replay
+/// and does not replace the last nonce. A nonce that goes further backwards is taken to be a
+/// device restart and is accepted. A device that restarts with a nonce just behind its last one
+/// is reported as a possible replay until its nonce has advanced the number of restart
+/// confirmations times without the last nonce being seen in between, and is then taken to have
+/// restarted. A replayer holding that many consecutive advertisements can pass them off as a
+/// restart, so the nonce only guards against simple replays.
+///
+/// The tracker has a fixed capacity of `N` devices so that it can be used without an allocator.
+/// When it is full, the device seen least recently is forgotten to make room.
+#[derive(Debug, Clone)]
+pub struct NonceTracker<A, const N: usize = DEFAULT_NONCE_TRACKER_CAPACITY> {
+    entries: [Option<NonceTrackerEntry<A>>; N],
+    replay_window: u16,
+    restart_confirmations: u8,
+    tick: u64,
+}
+
+#[derive(Debug, Clone)]
+struct NonceTrackerEntry<A> {
+    address: A,
+    nonce: u16,
+    last_seen: u64,
+    /// The last nonce behind `nonce` and the number of times in a row the nonce has advanced
+    /// behind `nonce`, while a possible restart is being confirmed.
+    pending_restart: Option<(u16, u8)>,
+}
+
+/// How the nonce of an advertisement relates to the last one seen from the same device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NonceStatus {
+    /// The first advertisement seen from the device.
+    First,
+    /// The nonce increased.
+    Advanced,
+    /// The nonce wrapped around from the top of its range to the bottom.
+    WrappedAround,
+    /// The nonce went back further than the replay window, or went back and then advanced the
+    /// number of restart confirmations times, which happens when the device restarts.
+    Restarted,
+    /// The same nonce as the last advertisement, so the content is unchanged.
+    Repeat,
+    /// The nonce went back by no more than the replay window. The advertisement may be an old
+    /// one sent again by another transmitter.
+    PossibleReplay { last_nonce: u16 },
+}
+
+impl NonceStatus {
+    /// Whether the advertisement carries new content and should be processed.
+    pub fn is_new(&self) -> bool {
+        matches!(
+            self,
+            Self::First | Self::Advanced | Self::WrappedAround | Self::Restarted
+        )
+    }
+}
+
+impl<A: PartialEq, const N: usize> NonceTracker<A, N> {
+    pub fn new() -> Self {
+        Self::with_replay_window(DEFAULT_REPLAY_WINDOW)
+    }
+
+    pub fn with_replay_window(replay_window: u16) -> Self {
+        Self {
+            entries: core::array::from_fn(|_| None),
+            replay_window,
+            restart_confirmations: DEFAULT_RESTART_CONFIRMATIONS,
+            tick: 0,
+        }
+    }
+
+    /// Set the number of advancing nonces behind the last one after which a device is taken
+    /// to have restarted.
+    pub fn with_restart_confirmations(mut self, restart_confirmations: u8) -> Self {
+        self.restart_confirmations = restart_confirmations;
+        self
+    }
+
+    /// Record the nonce of an advertisement from the device with the given address,
+    /// returning how it relates to the last nonce seen from that device.
+    pub fn observe(&mut self, address: A, nonce: u16) -> NonceStatus {
+        self.tick += 1;
+        let tick = self.tick;
+
+        let Some(entry) = self
+            .entries
+            .iter_mut()
+            .flatten()
+            .find(|entry| entry.address == address)
+        else {
+            self.push(NonceTrackerEntry {
+                address,
+                nonce,
+                last_seen: tick,
+                pending_restart: None,
+            });
+            return NonceStatus::First;
+        };
+
+        entry.last_seen = tick;
+        let last_nonce = entry.nonce;
+        let forward = nonce.wrapping_sub(last_nonce);
+        let backward = last_nonce.wrapping_sub(nonce);
+
+        let status = if forward == 0 {
+            NonceStatus::Repeat
+        } else if backward <= self.replay_window {
+            let confirmations = match entry.pending_restart {
+                Some((pending_nonce, _)) if pending_nonce == nonce => None,
+                Some((pending_nonce, count)) if nonce.wrapping_sub(pending_nonce) < 0x8000 => {
+                    Some(count.saturating_add(1))
+                }
+                _ => Some(1),
+            };
+            match confirmations {
+                Some(count) if count >= self.restart_confirmations => NonceStatus::Restarted,
+                Some(count) => {
+                    entry.pending_restart = Some((nonce, count));
+                    NonceStatus::PossibleReplay { last_nonce }
+                }
+                None => NonceStatus::PossibleReplay { last_nonce },
+            }
+        } else if nonce > last_nonce {
+            NonceStatus::Advanced
+        } else if forward < 0x8000 {
+            NonceStatus::WrappedAround
+        } else {
+            NonceStatus::Restarted
+        };
+
+        if status.is_new() {
+            entry.nonce = nonce;
+        }
+        if !matches!(status, NonceStatus::PossibleReplay { .. }) {
+            entry.pending_restart = None;
+        }
+        status
+    }
+
+    /// Forget the device with the given address. Returns whether it was being tracked.
+    pub fn remove(&mut self, address: &A) -> bool {
+        for slot in self.entries.iter_mut() {
+            if slot.as_ref().is_some_and(|entry| entry.address == *address) {
+                *slot = None;
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&mut self, entry: NonceTrackerEntry<A>) {
+        let Some(slot) = self
+            .entries
+            .iter_mut()
+            .min_by_key(|slot| slot.as_ref().map_or(0, |entry| entry.last_seen))
+        else {
+            // A tracker with no capacity tracks nothing.
+            return;
+        };
+        *slot = Some(entry);
+    }
+}
+
+impl<A: PartialEq, const N: usize> Default for NonceTracker<A, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_nonce_tracker_observe() {
+        let mut tracker = NonceTracker::<u8, 4>::with_replay_window(16);
+
+        assert_eq!(tracker.observe(1, 0x1000), NonceStatus::First);
+        assert_eq!(tracker.observe(1, 0x1000), NonceStatus::Repeat);
+        assert_eq!(tracker.observe(1, 0x1001), NonceStatus::Advanced);
+        assert_eq!(tracker.observe(1, 0x1400), NonceStatus::Advanced);
+        assert_eq!(
+            tracker.observe(1, 0x13F0),
+            NonceStatus::PossibleReplay { last_nonce: 0x1400 }
+        );
+        assert_eq!(tracker.observe(1, 0x1400), NonceStatus::Repeat);
+        assert_eq!(tracker.observe(1, 0x0002), NonceStatus::Restarted);
+        assert_eq!(tracker.observe(1, 0x0003), NonceStatus::Advanced);
+        assert_eq!(tracker.observe(2, 0x0003), NonceStatus::First);
+    }
+
+    #[test]
+    fn test_nonce_tracker_restart_behind_last_nonce() {
+        let mut tracker =
+            NonceTracker::<u8, 4>::with_replay_window(16).with_restart_confirmations(3);
+        let replay = NonceStatus::PossibleReplay { last_nonce: 0x1000 };
+
+        assert_eq!(tracker.observe(1, 0x1000), NonceStatus::First);
+        assert_eq!(tracker.observe(1, 0x0FF8), replay);
+        assert_eq!(tracker.observe(1, 0x0FF8), replay);
+        assert_eq!(tracker.observe(1, 0x0FF9), replay);
+        // Seeing the last nonce again means the device did not restart.
+        assert_eq!(tracker.observe(1, 0x1000), NonceStatus::Repeat);
+        assert_eq!(tracker.observe(1, 0x0FF9), replay);
+        assert_eq!(tracker.observe(1, 0x0FFA), replay);
+        assert_eq!(tracker.observe(1, 0x0FFC), NonceStatus::Restarted);
+        assert_eq!(tracker.observe(1, 0x0FFC), NonceStatus::Repeat);
+        assert_eq!(tracker.observe(1, 0x0FFD), NonceStatus::Advanced);
+    }
+
+    #[test]
+    fn test_nonce_tracker_wrap_around() {
+        let mut tracker = NonceTracker::<u8, 4>::with_replay_window(16);
+
+        assert_eq!(tracker.observe(1, 0xFFFE), NonceStatus::First);
+        assert_eq!(tracker.observe(1, 0x0001), NonceStatus::WrappedAround);
+        assert_eq!(
+            tracker.observe(1, 0xFFFF),
+            NonceStatus::PossibleReplay { last_nonce: 0x0001 }
+        );
+        assert_eq!(tracker.observe(1, 0x0002), NonceStatus::Advanced);
+    }
+
+    #[test]
+    fn test_nonce_tracker_forgets_least_recently_seen() {
+        let mut tracker = NonceTracker::<u8, 2>::new();
+
+        tracker.observe(1, 10);
+        tracker.observe(2, 20);
+        tracker.observe(1, 10);
+        tracker.observe(3, 30);
+
+        assert_eq!(tracker.len(), 2);
+        assert_eq!(tracker.observe(1, 10), NonceStatus::Repeat);
+        assert!(tracker.remove(&3));
+        assert!(!tracker.remove(&2));
+        assert_eq!(tracker.observe(2, 20), NonceStatus::First);
+    }
+}
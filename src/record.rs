@@ -1,21 +1,33 @@
+use crate::decoder::Decoder;
 use crate::err::*;
+use crate::model::AdvertisementHeader;
 use aes::cipher::StreamCipher;
-use ctr::cipher::KeyIvInit;
+use ctr::cipher::InnerIvInit;
 
 pub(crate) const RECORD_TYPE_TEST_RECORD: u8 = 0x00;
 pub(crate) const RECORD_TYPE_SOLAR_CHARGER: u8 = 0x01;
 pub(crate) const RECORD_TYPE_BATTERY_MONITOR: u8 = 0x02;
 pub(crate) const RECORD_TYPE_INVERTER: u8 = 0x03;
+pub(crate) const RECORD_TYPE_DC_DC_CONVERTER: u8 = 0x04;
+pub(crate) const RECORD_TYPE_SMART_LITHIUM: u8 = 0x05;
+pub(crate) const RECORD_TYPE_INVERTER_RS: u8 = 0x06;
+pub(crate) const RECORD_TYPE_GX_DEVICE: u8 = 0x07;
 pub(crate) const RECORD_TYPE_AC_CHARGER: u8 = 0x08;
+pub(crate) const RECORD_TYPE_SMART_BATTERY_PROTECT: u8 = 0x09;
+pub(crate) const RECORD_TYPE_LYNX_SMART_BMS: u8 = 0x0A;
+pub(crate) const RECORD_TYPE_MULTI_RS: u8 = 0x0B;
 pub(crate) const RECORD_TYPE_VE_BUS: u8 = 0x0C;
+pub(crate) const RECORD_TYPE_DC_ENERGY_METER: u8 = 0x0D;
+pub(crate) const RECORD_TYPE_ORION_XS: u8 = 0x0F;
 
-const MANUFACTURER_DATA_RECORD_TYPE: u8 = 0x10;
+pub(crate) const MANUFACTURER_DATA_RECORD_TYPE: u8 = 0x10;
 
 type EncryptionAlgorithm = ctr::Ctr128LE<aes::Aes128>;
 
 pub(crate) struct Record<'d, 'k> {
+    header: AdvertisementHeader,
     data: &'d [u8],
-    encryption_key: &'k [u8],
+    decoder: &'k Decoder,
 }
 
 /// The content of a Victron extra manufacturer data record. Provides
@@ -28,26 +40,19 @@ pub(crate) struct Record<'d, 'k> {
 /// Bytes | Value | Meaning
 /// 0     | 0x10  | This is a Victron device status message
 /// 1     | ?     | ?
-/// 2-3   | ?     | Device model ID. (Not used in this crate.)
+/// 2-3   | ?     | Product ID (device model ID) in little endian form.
 /// 4     | ?     | Record type, such as SolarCharger or Inverter.
 /// 5-6   | ?     | The IV used in decryption in little endian form.
 /// 7     | ?     | The first byte of the decryption key. Used to validate the given decryption key.
 /// 8..   | ?     | Payload encrypted using AES128 in CTR mode with the given IV.
 impl<'d, 'k> Record<'d, 'k> {
-    pub(crate) fn new(data: &'d [u8], encryption_key: &'k [u8]) -> Result<Self> {
+    pub(crate) fn new(data: &'d [u8], decoder: &'k Decoder) -> Result<Self> {
         let record = Self {
+            header: AdvertisementHeader::parse(data)?,
             data,
-            encryption_key,
+            decoder,
         };
 
-        if record.is_data_too_big() {
-            return Err(Error::RecordTooBig)
-        }
-
-        if !record.is_victron_extra_manufacturer_data() {
-            return Err(Error::WrongAdvertisement);
-        }
-
         if !record.is_correct_encryption_key() {
             return Err(Error::IncorrectDeviceEncryptionKey);
         }
@@ -56,69 +61,101 @@ impl<'d, 'k> Record<'d, 'k> {
     }
 
     pub(crate) fn decrypt(&self) -> Result<[u8; 16]> {
-        let mut algo = EncryptionAlgorithm::new(self.encryption_key.into(), &self.iv().into());
-
-        let cipher = self.cipher();
         let mut data = [0; 16];
-        algo.apply_keystream_b2b(&cipher, &mut data)?;
-
+        self.decrypt_into(&mut data)?;
         Ok(data)
     }
 
-    pub(crate) fn record_type(&self) -> u8 {
-        self.data[4]
+    pub(crate) fn decrypt_into(&self, payload: &mut [u8; 16]) -> Result<()> {
+        // Start from the cipher initialised by the decoder rather than expanding the key again.
+        let mut algo = EncryptionAlgorithm::from_core(ctr::CtrCore::inner_iv_init(
+            self.decoder.cipher().clone(),
+            &self.iv().into(),
+        ));
+
+        let cipher = self.cipher()?;
+        algo.apply_keystream_b2b(&cipher, payload)?;
+
+        Ok(())
     }
 
-    fn is_data_too_big(&self) -> bool {
-        self.data.len() > 24
+    pub(crate) fn header(&self) -> &AdvertisementHeader {
+        &self.header
     }
 
-    fn is_victron_extra_manufacturer_data(&self) -> bool {
-        self.data[0] == MANUFACTURER_DATA_RECORD_TYPE
+    pub(crate) fn record_type(&self) -> u8 {
+        self.header.record_type
     }
 
     fn iv(&self) -> [u8; 16] {
-        [
-            self.data[5],
-            self.data[6],
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-            0,
-        ]
+        iv(self.header.nonce)
     }
 
     fn is_correct_encryption_key(&self) -> bool {
-        self.data[7] == self.encryption_key[0]
+        self.header.is_key_candidate(self.decoder.key())
     }
 
-    fn cipher(&self) -> [u8; 16] {
-        let data = &self.data[8..];
+    fn cipher(&self) -> Result<[u8; 16]> {
+        let data = self.data.get(8..).ok_or(Error::DataTooShort)?;
         let data_len = data.len();
-        assert!(data_len <= 16);
+        if data_len > 16 {
+            return Err(Error::RecordTooBig);
+        }
 
         let mut padded = [0u8; 16];
         padded[..data_len].copy_from_slice(data);
         let pad_value = 16 - data_len as u8;
         padded[data_len..].fill(pad_value);
 
-        padded
+        Ok(padded)
     }
 }
 
+/// Encrypt a payload and prepend the given header, producing the manufacturer
+/// data that a Victron device would publish. The inverse of `Record::decrypt`.
+pub(crate) fn encrypt(
+    header: &AdvertisementHeader,
+    payload: &[u8; 16],
+    decoder: &Decoder,
+) -> Result<[u8; 24]> {
+    let mut algo = EncryptionAlgorithm::from_core(ctr::CtrCore::inner_iv_init(
+        decoder.cipher().clone(),
+        &iv(header.nonce).into(),
+    ));
+
+    let mut cipher = [0; 16];
+    algo.apply_keystream_b2b(payload, &mut cipher)?;
+
+    let product_id = header.product_id.to_le_bytes();
+    let nonce = header.nonce.to_le_bytes();
+    let mut data = [0; 24];
+    data[..8].copy_from_slice(&[
+        MANUFACTURER_DATA_RECORD_TYPE,
+        0x00,
+        product_id[0],
+        product_id[1],
+        header.record_type,
+        nonce[0],
+        nonce[1],
+        decoder.key().key_check(),
+    ]);
+    data[8..].copy_from_slice(&cipher);
+
+    Ok(data)
+}
+
+fn iv(nonce: u16) -> [u8; 16] {
+    let nonce = nonce.to_le_bytes();
+    let mut iv = [0; 16];
+    iv[..2].copy_from_slice(&nonce);
+    iv
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::EncryptionKey;
+    use ctr::cipher::KeyIvInit;
 
     #[test]
     fn test_decrypt() {
@@ -157,8 +194,31 @@ mod test {
             cipher[14],
             cipher[15],
         ];
-        let record = Record::new(&manufacturer_data, &key).unwrap();
+        let decoder = Decoder::new(&EncryptionKey::new(key));
+        let record = Record::new(&manufacturer_data, &decoder).unwrap();
         let decrypted = record.decrypt().unwrap();
         assert_eq!(decrypted, plaintext);
     }
+
+    #[test]
+    fn test_encrypt() {
+        let plaintext = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let key = EncryptionKey::new([
+            17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
+        ]);
+        let header = AdvertisementHeader {
+            product_id: 0xA389,
+            record_type: RECORD_TYPE_TEST_RECORD,
+            nonce: 0x2221,
+            key_check: 17,
+        };
+
+        let decoder = Decoder::new(&key);
+
+        let manufacturer_data = encrypt(&header, &plaintext, &decoder).unwrap();
+
+        let record = Record::new(&manufacturer_data, &decoder).unwrap();
+        assert_eq!(*record.header(), header);
+        assert_eq!(record.decrypt().unwrap(), plaintext);
+    }
 }